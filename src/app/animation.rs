@@ -0,0 +1,253 @@
+use std::time::{Duration, Instant};
+
+use crate::freedesktop::settings::prefers_reduced_motion;
+
+// Interval between two animation frames
+pub const FRAME: Duration = Duration::from_millis(16);
+
+// Distance the window content slides from when opening
+const OPEN_SLIDE_DISTANCE: f32 = 32.0;
+
+// Kinetic scrolling velocity decays exponentially with this time constant, in seconds
+const KINETIC_TIME_CONSTANT: f32 = 0.325;
+// Kinetic scrolling stops below this velocity, in pixels per second
+const KINETIC_MIN_VELOCITY: f32 = 20.0;
+
+// A value eased from `from` to `to` over `duration`
+#[derive(Debug, Clone, Copy)]
+pub struct Transition {
+    from: f32,
+    to: f32,
+    start: Instant,
+    duration: Duration,
+}
+
+impl Transition {
+    pub fn new(from: f32, to: f32, duration: Duration, now: Instant) -> Self {
+        Transition {
+            from,
+            to,
+            start: now,
+            duration,
+        }
+    }
+
+    pub fn value(&self, now: Instant) -> f32 {
+        self.from + (self.to - self.from) * ease_out_cubic(self.progress(now))
+    }
+
+    pub fn is_done(&self, now: Instant) -> bool {
+        self.progress(now) >= 1.0
+    }
+
+    fn progress(&self, now: Instant) -> f32 {
+        if self.duration.is_zero() {
+            return 1.0;
+        }
+
+        let elapsed = now.saturating_duration_since(self.start);
+        (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
+
+fn ease_out_cubic(t: f32) -> f32 {
+    1.0 - (1.0 - t).powi(3)
+}
+
+// Distance travelled during `elapsed` seconds and the decayed velocity
+fn kinetic_step(velocity: f32, elapsed: f32) -> (f32, f32) {
+    let decayed = velocity * (-elapsed / KINETIC_TIME_CONSTANT).exp();
+    let distance = (velocity - decayed) * KINETIC_TIME_CONSTANT;
+    (distance, decayed)
+}
+
+// Themes durations, disabled when the system prefers reduced motion.
+// The system setting is only read once an animation is configured
+fn duration(duration: Duration) -> Duration {
+    if duration.is_zero() || prefers_reduced_motion() {
+        Duration::ZERO
+    } else {
+        duration
+    }
+}
+
+#[derive(Debug)]
+pub struct Animations {
+    now: Instant,
+    // Selection highlight moving from the previously selected row
+    highlight: Option<(Option<usize>, Transition)>,
+    // Absolute scroll offset, either animated or the last known position
+    scroll: Option<Transition>,
+    scroll_offset: f32,
+    // Velocity of a kinetic scroll, in pixels per second
+    kinetic: Option<f32>,
+    open: Option<Transition>,
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Animations {
+            now: Instant::now(),
+            highlight: None,
+            scroll: None,
+            scroll_offset: 0.0,
+            kinetic: None,
+            open: None,
+        }
+    }
+}
+
+impl Animations {
+    pub fn is_running(&self) -> bool {
+        self.highlight.is_some()
+            || self.scroll.is_some()
+            || self.kinetic.is_some()
+            || self.open.is_some()
+    }
+
+    // Advance all animations, returns the scroll offset to apply if the scroll animation is running
+    pub fn tick(&mut self, now: Instant) -> Option<f32> {
+        let elapsed = now.saturating_duration_since(self.now).as_secs_f32();
+        self.now = now;
+
+        if matches!(self.highlight, Some((_, transition)) if transition.is_done(now)) {
+            self.highlight = None;
+        }
+
+        if matches!(self.open, Some(transition) if transition.is_done(now)) {
+            self.open = None;
+        }
+
+        if let Some(velocity) = self.kinetic {
+            let (distance, velocity) = kinetic_step(velocity, elapsed);
+            self.scroll_offset += distance;
+            self.kinetic = (velocity.abs() > KINETIC_MIN_VELOCITY).then_some(velocity);
+            return Some(self.scroll_offset);
+        }
+
+        let transition = self.scroll?;
+        self.scroll_offset = transition.value(now);
+        if transition.is_done(now) {
+            self.scroll = None;
+        }
+
+        Some(self.scroll_offset)
+    }
+
+    pub fn open(&mut self, open_duration: Duration) {
+        let open_duration = duration(open_duration);
+        if !open_duration.is_zero() {
+            let now = Instant::now();
+            self.now = now;
+            self.open = Some(Transition::new(0.0, 1.0, open_duration, now));
+        }
+    }
+
+    pub fn select(&mut self, previous: Option<usize>, highlight_duration: Duration) {
+        let highlight_duration = duration(highlight_duration);
+        self.highlight = if highlight_duration.is_zero() {
+            None
+        } else {
+            let now = Instant::now();
+            self.now = now;
+            Some((previous, Transition::new(0.0, 1.0, highlight_duration, now)))
+        };
+    }
+
+    // Returns false if the scroll should be applied immediately
+    pub fn scroll_to(&mut self, offset: f32, scroll_duration: Duration) -> bool {
+        let scroll_duration = duration(scroll_duration);
+        self.kinetic = None;
+        if scroll_duration.is_zero() {
+            self.scroll = None;
+            self.scroll_offset = offset;
+            false
+        } else {
+            let now = Instant::now();
+            self.now = now;
+            self.scroll = Some(Transition::new(
+                self.scroll_offset,
+                offset,
+                scroll_duration,
+                now,
+            ));
+            true
+        }
+    }
+
+    // Keep scrolling after a touch drag, `velocity` is in pixels per second
+    pub fn fling(&mut self, velocity: f32) {
+        self.stop_scroll();
+        if velocity.abs() > KINETIC_MIN_VELOCITY && !prefers_reduced_motion() {
+            self.now = Instant::now();
+            self.kinetic = Some(velocity);
+        }
+    }
+
+    // Stop any scroll animation, the offset stays where it is
+    pub fn stop_scroll(&mut self) {
+        self.scroll = None;
+        self.kinetic = None;
+    }
+
+    // Keep track of scroll changes not originating from us (mouse wheel, scrollbar ...)
+    pub fn on_scrolled(&mut self, offset: f32) {
+        if self.scroll.is_none() {
+            self.scroll_offset = offset;
+        }
+    }
+
+    // How much a row is highlighted, 1.0 being fully selected
+    pub fn highlight(&self, selected: Option<usize>, idx: usize) -> f32 {
+        let is_selected = selected == Some(idx);
+        match self.highlight {
+            Some((previous, transition)) => {
+                let progress = transition.value(self.now);
+                if is_selected {
+                    progress
+                } else if previous == Some(idx) {
+                    1.0 - progress
+                } else {
+                    0.0
+                }
+            }
+            None if is_selected => 1.0,
+            None => 0.0,
+        }
+    }
+
+    // Vertical offset of the window content while opening
+    pub fn open_offset(&self) -> f32 {
+        self.open
+            .map(|transition| (1.0 - transition.value(self.now)) * OPEN_SLIDE_DISTANCE)
+            .unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::animation::{kinetic_step, Transition};
+    use speculoos::prelude::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn should_ease_transition() {
+        let start = Instant::now();
+        let transition = Transition::new(0.0, 10.0, Duration::from_millis(100), start);
+
+        assert_that!(transition.value(start)).is_equal_to(0.0);
+        assert_that!(transition.value(start + Duration::from_millis(50))).is_greater_than(5.0);
+        assert_that!(transition.value(start + Duration::from_millis(200))).is_equal_to(10.0);
+        assert_that!(transition.is_done(start + Duration::from_millis(100))).is_true();
+    }
+
+    #[test]
+    fn should_decay_kinetic_scroll() {
+        let (first, velocity) = kinetic_step(1000.0, 0.016);
+        let (second, _) = kinetic_step(velocity, 0.016);
+
+        assert_that!(first).is_greater_than(second);
+        assert_that!(first).is_less_than(16.0);
+        assert_that!(velocity).is_less_than(1000.0);
+    }
+}
@@ -30,7 +30,7 @@ impl Default for Cache<'_> {
 }
 
 impl Cache<'_> {
-    pub fn de_history(&self) -> &Vec<DesktopEntryEntity> {
+    pub fn de_history(&self) -> &Vec<DesktopEntryEntity<'_>> {
         self.de_history.get_or_init(|| {
             self.db
                 .get_all::<DesktopEntryEntity>(db::desktop_entry::COLLECTION)
@@ -41,7 +41,7 @@ impl Cache<'_> {
         self.de_history.get().map(|de| de.len()).unwrap_or(0)
     }
 
-    pub fn plugin_history(&self, plug_name: &str) -> Rc<Vec<PluginCommandEntity>> {
+    pub fn plugin_history(&self, plug_name: &str) -> Rc<Vec<PluginCommandEntity<'_>>> {
         let mut history = self.plugin_history.lock().unwrap();
         if history.get(plug_name).is_none() {
             let data = self.db.get_all::<PluginCommandEntity>(plug_name);
@@ -60,7 +60,7 @@ impl Cache<'_> {
             .unwrap_or(0)
     }
 
-    pub fn web_history(&self, web_name: &str) -> Rc<Vec<WebEntity>> {
+    pub fn web_history(&self, web_name: &str) -> Rc<Vec<WebEntity<'_>>> {
         let mut history = self.web_history.lock().unwrap();
         if history.get(web_name).is_none() {
             let data = self.db.get_all::<WebEntity>(web_name);
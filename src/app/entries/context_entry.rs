@@ -0,0 +1,31 @@
+use iced::widget::Row;
+use onagre_launcher_toolkit::launcher::ContextOption;
+use std::borrow::Cow;
+
+use crate::app::entries::AsEntry;
+use crate::app::style::rows::RowStyles;
+use crate::app::Message;
+use crate::icons::IconPath;
+
+impl<'a> AsEntry<'a> for ContextOption {
+    // Context options are displayed with the icon of the result they apply to
+    fn get_icon_layout<'b>(
+        &'a self,
+        category_icon: Option<&'a IconPath>,
+        style: &'static RowStyles,
+    ) -> Row<'b, Message>
+    where
+        'b: 'a,
+    {
+        let icon = Self::build_icon(&style.icon, category_icon);
+        Row::new().push(icon)
+    }
+
+    fn get_display_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn get_description(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+}
@@ -1,6 +1,7 @@
 use crate::app::style::rows::button::ButtonStyle;
 use crate::app::style::rows::icon::IconStyle;
-use crate::app::style::rows::RowStyles;
+use crate::app::style::rows::{RowStyles, RowTransitionStyle};
+use crate::app::style::scrollable::{RowContainerStyle, RowsLayout};
 use crate::app::Message;
 use crate::icons::{fallback_icon, Extension, IconPath};
 use crate::THEME;
@@ -8,27 +9,48 @@ use iced::widget::{column, container, row, text, Button, Container, Image, Row};
 use iced::{Alignment, Length};
 use std::borrow::Cow;
 
+pub(crate) mod context_entry;
 pub(crate) mod db_entry;
+pub(crate) mod native_entry;
 pub(crate) mod pop_entry;
+pub(crate) mod theme_entry;
+
+// Glyphs are slightly smaller than the icon size so their line height fits in the icon slot
+const GLYPH_SCALE: f32 = 0.8;
 
 pub(crate) trait AsEntry<'a> {
+    // `highlight` goes from 0.0 (not selected) to 1.0 (selected) during selection transitions
     fn to_row<'b>(
         &'a self,
-        selected: Option<usize>,
+        highlight: f32,
         idx: usize,
         category_icon: Option<&'a IconPath>,
+        rows: &'static RowContainerStyle,
     ) -> Container<'b, Message>
     where
         'b: 'a,
     {
-        let theme = self.get_style(selected, idx);
+        let theme = self.get_style(highlight, rows);
+        let style = if highlight > 0.0 && highlight < 1.0 {
+            iced::theme::Container::Custom(Box::new(RowTransitionStyle {
+                from: &rows.row,
+                to: &rows.row_selected,
+                progress: highlight,
+            }))
+        } else {
+            iced::theme::Container::Custom(Box::new(theme))
+        };
 
-        let row = if THEME.icon_theme.is_some() {
+        let row = if THEME.icon_theme.is_some() || self.get_glyph().is_some() {
             self.get_icon_layout(category_icon, theme)
         } else {
             Row::new()
         };
 
+        if rows.layout == RowsLayout::Grid {
+            return self.as_cell(row, theme, style, rows, idx);
+        }
+
         let row = row
             .height(Length::Shrink)
             .width(Length::Fill)
@@ -36,13 +58,14 @@ pub(crate) trait AsEntry<'a> {
             // See : https://github.com/iced-rs/iced/pull/1044
             .align_items(Alignment::Start);
 
-        self.as_row(row, theme, idx)
+        self.as_row(row, theme, style, idx)
     }
 
     fn as_row<'b>(
         &self,
         row: Row<'b, Message>,
         theme: &'static RowStyles,
+        style: iced::theme::Container,
         idx: usize,
     ) -> Container<'b, Message>
     where
@@ -84,7 +107,7 @@ pub(crate) trait AsEntry<'a> {
             .on_press(Message::Click(idx));
 
         Container::new(button)
-            .style(iced::theme::Container::Custom(Box::new(theme)))
+            .style(style)
             .padding(theme.padding.to_iced_padding())
             .width(theme.width)
             .height(theme.height)
@@ -92,12 +115,53 @@ pub(crate) trait AsEntry<'a> {
             .align_y(theme.align_y)
     }
 
-    fn get_style(&self, selected: Option<usize>, idx: usize) -> &'static RowStyles {
-        let selected = selected.map(|selected| selected == idx).unwrap_or(false);
-        if selected {
-            &THEME.app_container.rows.row_selected
+    // Grid cell with the icons above the title, descriptions are not displayed
+    fn as_cell<'b>(
+        &self,
+        icons: Row<'b, Message>,
+        theme: &'static RowStyles,
+        style: iced::theme::Container,
+        rows: &'static RowContainerStyle,
+        idx: usize,
+    ) -> Container<'b, Message>
+    where
+        'b: 'a,
+    {
+        let title = container(text(self.get_display_name()).size(theme.title.font_size))
+            .style(iced::theme::Container::Custom(Box::new(&theme.title)))
+            .padding(theme.title.padding.to_iced_padding())
+            .width(Length::Fill)
+            .center_x();
+
+        let cell = column(vec![icons.into(), title.into()])
+            .width(Length::Fill)
+            .spacing(theme.spacing)
+            .align_items(Alignment::Center);
+
+        let button = Button::new(cell)
+            .style(iced::theme::Button::Custom(Box::new(&ButtonStyle)))
+            .on_press(Message::Click(idx));
+
+        let width = rows
+            .cell_width
+            .map(|width| Length::Fixed(width as f32))
+            .unwrap_or(Length::Fill);
+
+        Container::new(button)
+            .style(style)
+            .padding(theme.padding.to_iced_padding())
+            .width(width)
+            .height(theme.height)
+            .center_x()
+            .align_y(theme.align_y)
+    }
+
+    // The layout snaps to the selected style halfway through transitions
+    fn get_style(&self, highlight: f32, rows: &'static RowContainerStyle) -> &'static RowStyles {
+        if highlight >= 0.5 {
+            &rows.row_selected
         } else {
-            &THEME.app_container.rows.row
+            &rows.row
         }
     }
 
@@ -109,8 +173,10 @@ pub(crate) trait AsEntry<'a> {
     where
         'b: 'a,
     {
-        let icon = self.get_icon();
-        let icon = Self::build_icon(&style.icon, icon);
+        let icon = match self.get_glyph() {
+            Some(glyph) => Self::build_glyph(&style.icon, glyph),
+            None => Self::build_icon(&style.icon, self.get_icon()),
+        };
         let row = if !style.hide_category_icon {
             let category_icon = Self::build_icon(&style.category_icon, category_icon);
             Row::new().push(category_icon)
@@ -158,8 +224,26 @@ pub(crate) trait AsEntry<'a> {
             .padding(theme.padding.to_iced_padding())
     }
 
+    // A character drawn in the icon slot, at the icon size
+    fn build_glyph<'b>(theme: &'static IconStyle, glyph: &str) -> Container<'b, Message>
+    where
+        'b: 'a,
+    {
+        let size = Length::Fixed(theme.icon_size as f32);
+        Container::new(text(glyph.to_string()).size(theme.icon_size as f32 * GLYPH_SCALE))
+            .center_x()
+            .center_y()
+            .width(size)
+            .height(size)
+            .padding(theme.padding.to_iced_padding())
+    }
+
     fn get_display_name(&self) -> &str;
 
+    fn get_glyph(&self) -> Option<&str> {
+        None
+    }
+
     fn get_icon(&self) -> Option<IconPath> {
         unreachable!()
     }
@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+
+use crate::app::entries::AsEntry;
+use crate::app::native::NativeEntry;
+use crate::icons::IconPath;
+
+impl<'a> AsEntry<'a> for NativeEntry {
+    fn get_display_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn get_icon(&self) -> Option<IconPath> {
+        self.icon.clone()
+    }
+
+    fn get_glyph(&self) -> Option<&str> {
+        self.glyph.as_deref()
+    }
+
+    fn get_description(&self) -> Option<Cow<'_, str>> {
+        self.description.as_deref().map(Cow::Borrowed)
+    }
+}
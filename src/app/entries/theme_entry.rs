@@ -0,0 +1,31 @@
+use iced::widget::Row;
+use std::borrow::Cow;
+
+use crate::app::entries::AsEntry;
+use crate::app::style::rows::RowStyles;
+use crate::app::Message;
+use crate::config::themes::NamedTheme;
+use crate::icons::IconPath;
+
+impl<'a> AsEntry<'a> for NamedTheme {
+    // Themes have no icon of their own, use the mode icon instead
+    fn get_icon_layout<'b>(
+        &'a self,
+        category_icon: Option<&'a IconPath>,
+        style: &'static RowStyles,
+    ) -> Row<'b, Message>
+    where
+        'b: 'a,
+    {
+        let icon = Self::build_icon(&style.icon, category_icon);
+        Row::new().push(icon)
+    }
+
+    fn get_display_name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    fn get_description(&self) -> Option<Cow<'_, str>> {
+        Some(self.path.to_string_lossy())
+    }
+}
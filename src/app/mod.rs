@@ -1,39 +1,71 @@
-use std::path::Path;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::process::exit;
+use std::sync::Once;
 
 use iced::alignment::{Horizontal, Vertical};
 use iced::futures::channel::mpsc::{Sender, TrySendError};
-use iced::widget::{column, container, scrollable, text_input, Column, Container, Row, Text};
-use iced::{event, window, Application, Command, Element, Length, Settings, Subscription};
+use iced::widget::scrollable::Viewport;
+use iced::widget::{
+    column, container, mouse_area, scrollable, text_input, Column, Container, Image, Row, Space,
+    Svg, Text,
+};
+use iced::{
+    event, window, Application, Command, ContentFit, Element, Length, Settings, Subscription,
+};
 use iced_core::keyboard::key::Named;
 use iced_core::keyboard::Key;
-use iced_core::widget::operation::scrollable::RelativeOffset;
+use iced_core::widget::operation::scrollable::AbsoluteOffset;
 use iced_core::window::settings::PlatformSpecific;
-use iced_core::{Event, Font, Pixels, Size};
+use iced_core::{Event, Font, Pixels, Point, Size};
 use iced_style::Theme;
-use onagre_launcher_toolkit::launcher::{Request, Response};
+use onagre_launcher_toolkit::launcher::{ContextOption, Request, Response};
 use once_cell::sync::Lazy;
-use tracing::{debug, trace};
+use std::time::{Duration, Instant};
+use tracing::{debug, error, trace, warn};
 
+use crate::app::animation::Animations;
 use crate::app::entries::pop_entry::PopSearchResult;
 use crate::app::entries::AsEntry;
-use crate::app::mode::ActiveMode;
-use crate::app::state::{Selection, State};
+use crate::app::mode::{ActiveMode, THEMES_MODE, WEB_CONFIG};
+use crate::app::native::{run, Activation, NativeEntry};
+use crate::app::preview::{Preview, PreviewContent, PreviewSource};
+use crate::app::state::{BackendStatus, ContextMenu, Selection, State};
+use crate::app::status::StatusLine;
+use crate::app::style::preview::{PreviewPosition, PreviewStyles};
+use crate::app::style::scrollable::{RowContainerStyle, RowsLayout};
+use crate::app::style::status::StatusStyles;
+use crate::app::style::{AppLayout, ClickActivation};
 use crate::app::subscriptions::pop_launcher::{PopLauncherSubscription, SubscriptionMessage};
+use crate::app::viewport::ResultsViewport;
+use crate::app::widgets::background_image::with_background_image;
+use crate::app::widgets::grid::grid;
+use crate::app::widgets::measured::Measured;
+use crate::app::widgets::touch::{Gesture, TouchGestures};
+use crate::app::widgets::wheel::OnWheel;
+use crate::config::themes;
 use crate::db::desktop_entry::DesktopEntryEntity;
 use crate::db::plugin::PluginCommandEntity;
 use crate::db::web::WebEntity;
+use crate::freedesktop::clipboard::{copy_image_to_clipboard, copy_to_clipboard};
 use crate::freedesktop::desktop::DesktopEntry;
+use crate::freedesktop::keyboard::show_on_screen_keyboard;
 use crate::icons::IconPath;
-use crate::THEME;
+use crate::{MAX_RESULTS, THEME, THEME_PATH};
 
+pub mod animation;
 pub mod cache;
 pub mod entries;
 pub mod mode;
+pub mod native;
 pub mod plugin_matchers;
+pub mod preview;
 pub mod state;
+pub mod status;
 pub mod style;
 pub mod subscriptions;
+pub mod viewport;
+pub mod widgets;
 
 pub fn run(pre_value: Option<String>) -> iced::Result {
     debug!("Starting Onagre in debug mode");
@@ -54,7 +86,11 @@ pub fn run(pre_value: Option<String>) -> iced::Result {
             },
             decorations: false,
             resizable: false,
-            position: window::Position::Centered,
+            position: match THEME.layout {
+                AppLayout::Vertical => window::Position::Centered,
+                // Not honored by all Wayland compositors
+                AppLayout::Bar => window::Position::Specific(Point::ORIGIN),
+            },
             min_size: None,
             max_size: None,
             icon: None,
@@ -77,6 +113,14 @@ pub fn run(pre_value: Option<String>) -> iced::Result {
 pub struct Onagre<'a> {
     state: State<'a>,
     request_tx: Option<Sender<Request>>,
+    animations: Animations,
+    viewport: ResultsViewport,
+    // Last clicked result, to detect double clicks
+    last_click: Option<(usize, Instant)>,
+    // Wheel lines not turned into a selection move yet
+    wheel_lines: f32,
+    // The theme onagre started with, restored when leaving the theme picker
+    startup_theme: PathBuf,
 }
 
 #[derive(Debug, Clone)]
@@ -84,14 +128,38 @@ pub enum Message {
     Loading,
     InputChanged(String),
     Click(usize),
+    // Mouse interactions with a result
+    Hover(usize),
+    RightClick(usize),
+    MiddleClick(usize),
+    // Wheel lines scrolled over the results, positive when scrolling up
+    Wheel(f32),
+    Touch(Gesture),
     KeyboardEvent(Key),
     SubscriptionResponse(SubscriptionMessage),
     Unfocused,
+    Focused,
+    // The theme was reloaded for a new color scheme, the view has to be rebuilt
+    ThemeRefreshed,
+    Scrolled(Viewport),
+    // Height of a result, and of the visible results, once laid out
+    RowMeasured(usize, f32),
+    ResultsMeasured(f32),
+    // Entries loaded in the background by a native mode, for a query
+    NativeLoaded(String, String, Vec<NativeEntry>),
+    PreviewLoaded(PreviewSource, Option<Preview>),
+    Tick(Instant),
 }
 
+// Maximum delay between the two clicks of a double click
+const DOUBLE_CLICK_DELAY: Duration = Duration::from_millis(400);
+
 static INPUT_ID: Lazy<text_input::Id> = Lazy::new(text_input::Id::unique);
 static SCROLL_ID: Lazy<scrollable::Id> = Lazy::new(scrollable::Id::unique);
 
+// Requests dropped without pop-launcher are reported once
+static POP_LAUNCHER_MISSING: Once = Once::new();
+
 pub struct OnagreFlags {
     pre_value: Option<String>,
 }
@@ -109,11 +177,21 @@ impl Application for Onagre<'_> {
             onagre = Onagre {
                 state: State::with_mode(&pre_value),
                 request_tx: Default::default(),
+                animations: Default::default(),
+                viewport: Onagre::default_viewport(),
+                last_click: None,
+                wheel_lines: 0.0,
+                startup_theme: THEME_PATH.lock().unwrap().clone(),
             };
         } else {
             onagre = Onagre {
                 state: Default::default(),
                 request_tx: Default::default(),
+                animations: Default::default(),
+                viewport: Onagre::default_viewport(),
+                last_click: None,
+                wheel_lines: 0.0,
+                startup_theme: THEME_PATH.lock().unwrap().clone(),
             };
         }
 
@@ -128,8 +206,23 @@ impl Application for Onagre<'_> {
     }
 
     fn update(&mut self, message: Self::Message) -> Command<Self::Message> {
-        match message {
-            Message::Loading => text_input::focus(INPUT_ID.clone()),
+        // Animation frames, scroll events and measures never change the selection
+        let selection_may_change = !matches!(
+            message,
+            Message::Tick(_)
+                | Message::Scrolled(_)
+                | Message::Touch(_)
+                | Message::RowMeasured(..)
+                | Message::ResultsMeasured(_)
+        );
+        let command = match message {
+            Message::Loading => {
+                self.animations.open(THEME.transition_duration);
+                if THEME.on_screen_keyboard {
+                    show_on_screen_keyboard();
+                }
+                text_input::focus(INPUT_ID.clone())
+            }
             Message::InputChanged(input) => self.on_input_changed(input),
             Message::KeyboardEvent(event) => self.handle_input(event),
             Message::SubscriptionResponse(message) => self.on_pop_launcher_message(message),
@@ -140,93 +233,143 @@ impl Application for Onagre<'_> {
                     Command::none()
                 }
             }
-            Message::Click(row_idx) => {
-                match self.state.get_active_mode() {
-                    ActiveMode::History => self.state.selected = Selection::History(row_idx),
-                    _ => self.state.selected = Selection::PopLauncher(row_idx),
+            Message::Focused => {
+                // The system color scheme may have changed while the window was not focused
+                Command::perform(
+                    tokio::task::spawn_blocking(|| THEME.refresh_color_scheme()),
+                    |_| Message::ThemeRefreshed,
+                )
+            }
+            Message::ThemeRefreshed => Command::none(),
+            Message::Click(row_idx) => self.on_click(row_idx),
+            Message::Hover(row_idx) => self.on_hover(row_idx),
+            Message::RightClick(row_idx) => self.open_context_menu(row_idx),
+            Message::MiddleClick(row_idx) => {
+                self.state.selected = self.selection_at(row_idx);
+                self.complete_selected();
+                Command::none()
+            }
+            Message::Wheel(lines) => self.on_wheel(lines),
+            Message::Touch(gesture) => self.on_touch(gesture),
+            Message::Scrolled(viewport) => {
+                let offset = viewport.absolute_offset().y;
+                self.animations.on_scrolled(offset);
+                self.viewport.on_scrolled(offset);
+                self.viewport.set_height(viewport.bounds().height);
+                Command::none()
+            }
+            Message::RowMeasured(idx, height) => {
+                self.viewport.record(idx, height);
+                Command::none()
+            }
+            Message::ResultsMeasured(height) => {
+                self.viewport.set_height(height);
+                Command::none()
+            }
+            Message::NativeLoaded(mode_name, query, entries) => {
+                self.on_native_loaded(mode_name, query, entries)
+            }
+            Message::PreviewLoaded(source, preview) => {
+                // The selection may have changed while loading
+                if self.state.preview_source.as_ref() == Some(&source) {
+                    self.state.preview = preview;
                 }
-
-                self.on_execute()
+                Command::none()
             }
+            Message::Tick(now) => match self.animations.tick(now) {
+                Some(offset) => {
+                    // Kinetic scrolling stops at both ends of the results
+                    let clamped = self.clamp_offset(offset);
+                    if clamped != offset {
+                        self.animations.stop_scroll();
+                        self.animations.on_scrolled(clamped);
+                    }
+                    self.scroll_to(clamped)
+                }
+                None => Command::none(),
+            },
+        };
+
+        if selection_may_change {
+            Command::batch([command, self.refresh_preview()])
+        } else {
+            command
         }
     }
 
     fn view(&self) -> Element<'_, Self::Message> {
-        // Build rows from current mode search entries
-        let selected = self.selected();
-        let rows = match &self.state.get_active_mode() {
-            ActiveMode::Plugin {
-                plugin_name,
-                history,
-                ..
-            } if *history => {
-                let icon = self.state.plugin_matchers.get_plugin_icon(plugin_name);
-                self.state
-                    .cache
-                    .plugin_history(plugin_name)
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, entry)| entry.to_row(selected, idx, icon.as_ref()).into())
-                    .collect::<Vec<Element<'_, Self::Message>>>()
+        let rows_style = THEME.rows(self.state.get_active_mode());
+        let total = self.current_entries_len();
+        let results: Element<'_, Self::Message> = match THEME.layout {
+            AppLayout::Bar => {
+                let page = self.bar_page(rows_style);
+                let rows = self.build_rows(page.clone(), rows_style);
+                self.bar_results(rows, page, total, rows_style).into()
             }
-            ActiveMode::Web { modifier, .. } => {
-                let icon = self.state.plugin_matchers.get_plugin_icon("web");
-                self.state
-                    .cache
-                    .web_history(modifier)
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, entry)| entry.to_row(selected, idx, icon.as_ref()).into())
-                    .collect()
-            }
-            ActiveMode::History => {
-                let icon = self
-                    .state
-                    .plugin_matchers
-                    .get_plugin_icon("desktop_entries");
-                self.state
-                    .cache
-                    .de_history()
-                    .iter()
-                    .enumerate()
-                    .map(|(idx, entry)| entry.to_row(selected, idx, icon.as_ref()).into())
-                    .collect()
+            AppLayout::Vertical => {
+                // Only visible rows are built, the space taken by the others is left empty
+                let columns = self.grid_columns();
+                let visible = self.viewport.visible(total, columns);
+                let rows = self.build_rows(visible.range, rows_style);
+                let rows = match rows_style.layout {
+                    RowsLayout::List => column(rows),
+                    RowsLayout::Grid => grid(rows, columns),
+                };
+
+                let rows = Column::new()
+                    .push(Space::with_height(visible.space_before))
+                    .push(rows)
+                    .push(Space::with_height(visible.space_after));
+
+                // Scrollable element containing the rows
+                let scrollable = scrollable(rows)
+                    .id(SCROLL_ID.clone())
+                    .on_scroll(Message::Scrolled)
+                    .style(iced::theme::Scrollable::Custom(Box::new(
+                        THEME.scrollable(),
+                    )));
+
+                Measured::new(
+                    scrollable,
+                    Some(self.viewport.height()),
+                    Message::ResultsMeasured,
+                )
+                .into()
             }
-            _ => self
-                .state
-                .pop_search
-                .iter()
-                .map(|entry| {
-                    let icon = match &THEME.icon_theme {
-                        Some(theme) => entry
-                            .category_icon
-                            .as_ref()
-                            .and_then(|source| IconPath::from_source(source, theme)),
-                        _ => None,
-                    };
+        };
 
-                    PopSearchResult(entry)
-                        .to_row(selected, entry.id as usize, icon.as_ref())
-                        .into()
-                })
-                .collect(),
+        let results = if THEME.wheel_select {
+            OnWheel::new(results, Message::Wheel).into()
+        } else {
+            results
         };
 
-        // Scrollable element containing the rows
-        let scrollable =
-            scrollable(column(rows))
-                .id(SCROLL_ID.clone())
-                .style(iced::theme::Scrollable::Custom(Box::new(
-                    THEME.scrollable(),
-                )));
-
-        let scrollable = container(scrollable)
-            .style(iced::theme::Container::Custom(Box::new(
-                &THEME.app_container.rows,
-            )))
-            .padding(THEME.app_container.rows.padding.to_iced_padding())
-            .width(THEME.app_container.rows.width)
-            .height(THEME.app_container.rows.height); // TODO: add this to stylesheet
+        let scrollable = container(results)
+            .style(iced::theme::Container::Custom(Box::new(rows_style)))
+            .padding(rows_style.padding.to_iced_padding())
+            .width(rows_style.width)
+            .height(rows_style.height); // TODO: add this to stylesheet
+
+        let scrollable = with_background_image(scrollable, rows_style.background_image.as_deref());
+
+        let scrollable: Element<'_, Self::Message> = match (THEME.layout, THEME.preview()) {
+            (AppLayout::Vertical, Some(preview_style)) => {
+                let preview = self.preview_pane(preview_style);
+                match preview_style.position {
+                    PreviewPosition::Right => Row::new()
+                        .push(scrollable)
+                        .push(preview)
+                        .spacing(preview_style.spacing)
+                        .into(),
+                    PreviewPosition::Bottom => Column::new()
+                        .push(scrollable)
+                        .push(preview)
+                        .spacing(preview_style.spacing)
+                        .into(),
+                }
+            }
+            _ => scrollable,
+        };
 
         let text_input = text_input("Search", &self.state.input_value.input_display)
             .on_input(Message::InputChanged)
@@ -278,32 +421,64 @@ impl Application for Onagre<'_> {
             .width(THEME.search().width)
             .height(THEME.search().height);
 
-        let app_container = Container::new(
-            Column::new()
+        let search_bar =
+            with_background_image(search_bar, THEME.search().background_image.as_deref());
+
+        let status_bar = THEME.status().map(|style| self.status_bar(style));
+
+        let content: Element<'_, Self::Message> = match THEME.layout {
+            AppLayout::Vertical => Column::new()
                 .push(search_bar)
                 .push(scrollable)
-                .align_items(iced_core::Alignment::Start),
-        )
-        .padding(THEME.app().padding.to_iced_padding())
-        .style(iced::theme::Container::Custom(Box::new(THEME.app())))
-        .center_y()
-        .center_x();
+                .push_maybe(status_bar)
+                .align_items(iced_core::Alignment::Start)
+                .into(),
+            AppLayout::Bar => Row::new()
+                .push(search_bar)
+                .push(scrollable)
+                .push_maybe(status_bar)
+                .align_items(iced_core::Alignment::Center)
+                .into(),
+        };
+
+        let app_container = Container::new(content)
+            .padding(THEME.app().padding.to_iced_padding())
+            .style(iced::theme::Container::Custom(Box::new(THEME.app())))
+            .center_y()
+            .center_x();
+
+        let app_container =
+            with_background_image(app_container, THEME.app().background_image.as_deref());
+
+        // Slide the content in when opening
+        let mut padding = THEME.padding.to_iced_padding();
+        padding.top += self.animations.open_offset();
 
         let app_wrapper = Container::new(app_container)
             .center_y()
             .center_x()
             .height(Length::Fill)
             .width(Length::Fill)
-            .padding(THEME.padding.to_iced_padding())
-            .style(iced::theme::Container::Custom(Box::new(&*THEME)));
+            .padding(padding)
+            .style(iced::theme::Container::Custom(Box::new(&**THEME)));
+
+        let app_wrapper: Element<'_, Self::Message> = if THEME.touch {
+            TouchGestures::new(app_wrapper, Message::Touch).into()
+        } else {
+            app_wrapper.into()
+        };
 
-        app_wrapper.into()
+        with_background_image(app_wrapper, THEME.background_image.as_deref())
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
         let keyboard_event = Onagre::keyboard_event();
         let pop_launcher = PopLauncherSubscription::create().map(Message::SubscriptionResponse);
-        let subs = vec![keyboard_event, pop_launcher];
+        let mut subs = vec![keyboard_event, pop_launcher];
+        if self.animations.is_running() {
+            subs.push(iced::time::every(animation::FRAME).map(Message::Tick));
+        }
+
         Subscription::batch(subs)
     }
 }
@@ -357,6 +532,7 @@ impl Onagre<'_> {
     }
 
     fn on_input_changed(&mut self, input: String) -> Command<Message> {
+        let was_previewing = self.state.get_active_mode() == &ActiveMode::Themes;
         self.state.set_input(&input);
         self.state.selected = match self.state.get_active_mode() {
             // For those mode first line is unselected on change
@@ -364,64 +540,121 @@ impl Onagre<'_> {
             // the next search response, then activate it
             ActiveMode::Web { .. } | ActiveMode::History => Selection::Reset,
             ActiveMode::Plugin { history, .. } if *history => Selection::Reset,
+            ActiveMode::Themes | ActiveMode::Native { .. } => Selection::History(0),
             _ => Selection::PopLauncher(0),
         };
 
-        let _: Command<Message> = scrollable::snap_to(SCROLL_ID.clone(), RelativeOffset::START);
+        self.state.context_menu = None;
+        let scroll_to_top = self.on_results_replaced();
+
+        // The previewed theme is dropped when leaving the picker without choosing it
+        let scroll_to_top = if was_previewing && self.state.get_active_mode() != &ActiveMode::Themes
+        {
+            debug!("Restoring theme {:?}", self.startup_theme);
+            THEME.swap(&self.startup_theme);
+            Command::batch([scroll_to_top, Onagre::resize_to_theme()])
+        } else {
+            scroll_to_top
+        };
 
         match &self.state.get_active_mode() {
             ActiveMode::History => {}
+            ActiveMode::Themes => {
+                return Command::batch([
+                    self.preview_theme(),
+                    scroll_to_top,
+                    text_input::focus(INPUT_ID.clone()),
+                ])
+            }
+            ActiveMode::Native { .. } => {
+                return Command::batch([
+                    self.search_native(),
+                    scroll_to_top,
+                    text_input::focus(INPUT_ID.clone()),
+                ])
+            }
             _ => {
                 let value = self.state.get_input();
 
                 self.pop_request(Request::Search(value))
-                    .expect("Unable to send search request to pop-launcher")
+                    .expect("Unable to send search request to pop-launcher");
+                self.state.backend = BackendStatus::Searching;
             }
         }
 
-        text_input::focus(INPUT_ID.clone())
+        Command::batch([scroll_to_top, text_input::focus(INPUT_ID.clone())])
     }
 
     fn run_command<P: AsRef<Path>>(&self, desktop_entry_path: P) -> Command<Message> {
-        let desktop_entry = DesktopEntry::from_path(&desktop_entry_path).unwrap();
+        let path = desktop_entry_path.as_ref();
+        let Some(desktop_entry) = DesktopEntry::from_path(path) else {
+            error!("Unable to read desktop entry {path:?}");
+            return Command::none();
+        };
 
-        DesktopEntryEntity::persist(
-            &desktop_entry,
-            desktop_entry_path.as_ref(),
-            &self.state.cache.db,
-        );
+        let args = match shell_words::split(&desktop_entry.exec) {
+            Ok(args) => args,
+            Err(err) => {
+                error!("Invalid Exec key in {path:?}: {err}");
+                return Command::none();
+            }
+        };
 
-        let argv = shell_words::split(&desktop_entry.exec);
-        let args = argv.unwrap();
-        let args = args
-            .iter()
+        let mut args = args
+            .into_iter()
             // Filter out special freedesktop syntax
             .filter(|entry| !entry.starts_with('%'))
-            .collect::<Vec<&String>>();
+            .collect::<Vec<String>>();
 
-        std::process::Command::new(args[0])
-            .args(&args[1..])
-            .spawn()
-            .expect("Command failure");
+        if desktop_entry.terminal.as_deref() == Some("true") {
+            match run::default_terminal(&run::path_dirs()) {
+                Some(terminal) => {
+                    args.splice(0..0, [terminal, "-e".to_string()]);
+                }
+                None => warn!("No terminal found to launch {path:?}, launching it without"),
+            }
+        }
 
-        exit(0);
+        let Some((program, args)) = args.split_first() else {
+            error!("Empty Exec key in {path:?}");
+            return Command::none();
+        };
+
+        match std::process::Command::new(program).args(args).spawn() {
+            Ok(_) => {
+                DesktopEntryEntity::persist(&desktop_entry, path, &self.state.cache.db);
+                exit(0);
+            }
+            Err(err) => {
+                error!("Failed to launch {path:?}: {err}");
+                Command::none()
+            }
+        }
     }
 
     fn handle_input(&mut self, key_code: Key) -> Command<Message> {
+        if self.state.context_menu.is_some() {
+            return self.handle_context_menu_input(key_code);
+        }
+
         match key_code {
             Key::Named(Named::ArrowUp) => {
                 trace!("Selected line : {:?}", self.selected());
-                return self.dec_selected();
+                return self.dec_selected(self.grid_columns());
             }
             Key::Named(Named::ArrowDown) => {
                 trace!("Selected line : {:?}", self.selected());
-                return self.inc_selected();
+                return self.inc_selected(self.grid_columns());
             }
+            // Left and right only navigate between grid cells or in the bar layout,
+            // otherwise they move the search input cursor
+            Key::Named(Named::ArrowLeft) if self.is_horizontal() => return self.dec_selected(1),
+            Key::Named(Named::ArrowRight) if self.is_horizontal() => return self.inc_selected(1),
             Key::Named(Named::Enter) => return self.on_execute(),
-            Key::Named(Named::Tab) => {
+            Key::Named(Named::Tab) => self.complete_selected(),
+            Key::Named(Named::ContextMenu) => {
                 if let Some(selected) = self.selected() {
-                    self.pop_request(Request::Complete(selected as u32))
-                        .expect("Unable to send request to pop-launcher");
+                    return self.open_context_menu(selected);
                 }
             }
             Key::Named(Named::Escape) => {
@@ -433,30 +666,769 @@ impl Onagre<'_> {
         Command::none()
     }
 
+    // The context menu takes over navigation until an option is activated or it is closed
+    fn handle_context_menu_input(&mut self, key_code: Key) -> Command<Message> {
+        let Some(menu) = self.state.context_menu.as_mut() else {
+            return Command::none();
+        };
+
+        match key_code {
+            Key::Named(Named::ArrowUp) => menu.selected = menu.selected.saturating_sub(1),
+            Key::Named(Named::ArrowDown) => {
+                menu.selected = (menu.selected + 1).min(menu.options.len().saturating_sub(1))
+            }
+            Key::Named(Named::Enter) => return self.activate_context_option(None),
+            Key::Named(Named::Escape) => {
+                self.state.context_menu = None;
+                return self.on_results_replaced();
+            }
+            _ => {}
+        };
+
+        Command::none()
+    }
+
+    fn complete_selected(&mut self) {
+        let Some(selected) = self.selected() else {
+            return;
+        };
+
+        if let ActiveMode::Native { mode_name, .. } = self.state.get_active_mode() {
+            let completion = self
+                .state
+                .native_modes
+                .get(mode_name)
+                .zip(self.state.native_search.get(selected))
+                .and_then(|(mode, entry)| mode.complete(entry));
+
+            if let Some(completion) = completion {
+                let modifier = &self.state.input_value.modifier_display;
+                self.complete(format!("{modifier}{completion}"));
+            }
+        } else {
+            self.pop_request(Request::Complete(selected as u32))
+                .expect("Unable to send request to pop-launcher");
+        }
+    }
+
+    fn selection_at(&self, idx: usize) -> Selection {
+        match self.state.get_active_mode() {
+            ActiveMode::History | ActiveMode::Themes | ActiveMode::Native { .. } => {
+                Selection::History(idx)
+            }
+            _ => Selection::PopLauncher(idx),
+        }
+    }
+
+    fn on_click(&mut self, idx: usize) -> Command<Message> {
+        if self.state.context_menu.is_some() {
+            return self.activate_context_option(Some(idx));
+        }
+
+        let now = Instant::now();
+        let activate = match THEME.click_activation {
+            ClickActivation::Single => true,
+            ClickActivation::Double => matches!(
+                self.last_click,
+                Some((last, at)) if last == idx && now.duration_since(at) <= DOUBLE_CLICK_DELAY
+            ),
+        };
+        self.last_click = Some((idx, now));
+
+        if activate {
+            self.state.selected = self.selection_at(idx);
+            self.on_execute()
+        } else {
+            let previous = self.selected();
+            self.state.selected = self.selection_at(idx);
+            self.highlight_selection(previous)
+        }
+    }
+
+    // The hovered row is visible already, the selection moves without scrolling
+    fn on_hover(&mut self, idx: usize) -> Command<Message> {
+        if let Some(menu) = self.state.context_menu.as_mut() {
+            menu.selected = idx;
+            return Command::none();
+        }
+
+        let previous = self.selected();
+        self.state.selected = self.selection_at(idx);
+        self.highlight_selection(previous)
+    }
+
+    fn on_wheel(&mut self, lines: f32) -> Command<Message> {
+        self.wheel_lines += lines;
+        let steps = self.wheel_lines.trunc();
+        self.wheel_lines -= steps;
+
+        if let Some(menu) = self.state.context_menu.as_mut() {
+            let last = menu.options.len().saturating_sub(1);
+            menu.selected = if steps > 0.0 {
+                menu.selected.saturating_sub(steps as usize)
+            } else {
+                (menu.selected + (-steps) as usize).min(last)
+            };
+            return Command::none();
+        }
+
+        // A wheel line moves the selection by a line of results
+        let step = self.grid_columns();
+        let commands: Vec<Command<Message>> = (0..steps.abs() as usize)
+            .map(|_| {
+                if steps > 0.0 {
+                    self.dec_selected(step)
+                } else {
+                    self.inc_selected(step)
+                }
+            })
+            .collect();
+
+        Command::batch(commands)
+    }
+
+    fn on_touch(&mut self, gesture: Gesture) -> Command<Message> {
+        match gesture {
+            Gesture::Drag(delta) => {
+                self.animations.stop_scroll();
+                let offset = self.clamp_offset(self.viewport.offset() + delta);
+                self.animations.on_scrolled(offset);
+                self.scroll_to(offset)
+            }
+            Gesture::Fling(velocity) => {
+                self.animations.fling(velocity);
+                Command::none()
+            }
+            Gesture::Swipe => exit(0),
+        }
+    }
+
+    fn clamp_offset(&self, offset: f32) -> f32 {
+        let max = self
+            .viewport
+            .max_offset(self.current_entries_len(), self.grid_columns());
+        offset.clamp(0.0, max)
+    }
+
+    fn open_context_menu(&mut self, idx: usize) -> Command<Message> {
+        if self.state.context_menu.is_some() {
+            return Command::none();
+        }
+
+        if let ActiveMode::Native { mode_name, .. } = self.state.get_active_mode() {
+            return self.open_native_context_menu(mode_name.clone(), idx);
+        }
+
+        if !self.state.shows_pop_results() {
+            return Command::none();
+        }
+
+        let previous = self.selected();
+        self.state.selected = self.selection_at(idx);
+        self.pop_request(Request::Context(idx as u32))
+            .expect("Unable to send context request to pop-launcher");
+        self.highlight_selection(previous)
+    }
+
+    // Native modes build their context options themselves
+    fn open_native_context_menu(&mut self, mode_name: String, idx: usize) -> Command<Message> {
+        let options = self
+            .state
+            .native_modes
+            .get(&mode_name)
+            .zip(self.state.native_search.get(idx))
+            .map(|(mode, entry)| mode.context(entry))
+            .unwrap_or_default();
+
+        if options.is_empty() {
+            return Command::none();
+        }
+
+        self.state.selected = self.selection_at(idx);
+        self.state.context_menu = Some(ContextMenu {
+            result: idx as u32,
+            options: options
+                .into_iter()
+                .enumerate()
+                .map(|(id, name)| ContextOption {
+                    id: id as u32,
+                    name,
+                })
+                .collect(),
+            selected: 0,
+        });
+
+        self.on_results_replaced()
+    }
+
+    // Activate the option at `idx`, or the selected one
+    fn activate_context_option(&mut self, idx: Option<usize>) -> Command<Message> {
+        if let Some(menu) = self.state.context_menu.take() {
+            let selected = idx.unwrap_or(menu.selected);
+            if let ActiveMode::Native { mode_name, .. } = &self.state.input_value.mode {
+                let option = menu.options.get(selected).map(|option| option.id as usize);
+                let native_search = &self.state.native_search;
+                let entry = native_search.get(menu.result as usize);
+                let activation = match (self.state.native_modes.get_mut(mode_name), entry, option) {
+                    (Some(mode), Some(entry), Some(option)) => mode.activate_context(entry, option),
+                    _ => Activation::None,
+                };
+
+                debug!("Native mode {mode_name} context activation: {activation:?}");
+                let command = self.run_activation(activation);
+                return Command::batch([command, self.on_results_replaced()]);
+            }
+
+            if let Some(option) = menu.options.get(selected) {
+                debug!(
+                    "Activating context option {} of entry {}",
+                    option.name, menu.result
+                );
+                self.pop_request(Request::ActivateContext {
+                    id: menu.result,
+                    context: option.id,
+                })
+                .expect("Unable to send context request to pop-launcher");
+            }
+        }
+
+        self.on_results_replaced()
+    }
+
+    // Query the active native mode, and load its entries in the background if it needs to
+    fn search_native(&mut self) -> Command<Message> {
+        self.state.search_native();
+
+        let query = self.state.mode_query().trim_end().to_string();
+        let ActiveMode::Native { mode_name, .. } = &self.state.input_value.mode else {
+            return Command::none();
+        };
+
+        let loading = self
+            .state
+            .native_modes
+            .get_mut(mode_name)
+            .and_then(|mode| mode.load(&query));
+
+        match loading {
+            Some(loading) => {
+                let mode_name = mode_name.clone();
+                Command::perform(loading, move |entries| {
+                    Message::NativeLoaded(mode_name, query, entries)
+                })
+            }
+            None => Command::none(),
+        }
+    }
+
+    fn on_native_loaded(
+        &mut self,
+        mode_name: String,
+        query: String,
+        entries: Vec<NativeEntry>,
+    ) -> Command<Message> {
+        if let Some(mode) = self.state.native_modes.get_mut(&mode_name) {
+            mode.on_loaded(&query, entries);
+        }
+
+        // The query may have changed in the meantime
+        match self.state.get_active_mode() {
+            ActiveMode::Native {
+                mode_name: active, ..
+            } if *active == mode_name => {
+                Command::batch([self.search_native(), self.on_results_replaced()])
+            }
+            _ => Command::none(),
+        }
+    }
+
+    // Results were replaced, forget measured rows and go back to the top
+    fn on_results_replaced(&mut self) -> Command<Message> {
+        let estimate = THEME
+            .rows(self.state.get_active_mode())
+            .estimated_row_height();
+        self.viewport.reset(estimate);
+        self.animations.on_scrolled(0.0);
+        self.scroll_to(0.0)
+    }
+
+    // Whether left and right arrows move the selection
+    fn is_horizontal(&self) -> bool {
+        THEME.layout == AppLayout::Bar
+            || THEME.rows(self.state.get_active_mode()).layout == RowsLayout::Grid
+    }
+
+    // Columns in the current layout, a list or a bar has a single one
+    fn grid_columns(&self) -> usize {
+        let rows = THEME.rows(self.state.get_active_mode());
+        match (THEME.layout, rows.layout) {
+            (AppLayout::Vertical, RowsLayout::Grid) => {
+                rows.grid_columns(THEME.rows_available_width())
+            }
+            _ => 1,
+        }
+    }
+
+    // The page of results containing the selection in the bar layout
+    fn bar_page(&self, rows_style: &RowContainerStyle) -> Range<usize> {
+        let page_size = rows_style.bar_items();
+        let start = self.selected().unwrap_or(0) / page_size * page_size;
+        start..(start + page_size).min(self.current_entries_len())
+    }
+
+    // A page of results laid out on a single line
+    // with indicators when results overflow on either side
+    fn bar_results<'a>(
+        &self,
+        rows: Vec<Element<'a, Message>>,
+        page: Range<usize>,
+        total: usize,
+        rows_style: &RowContainerStyle,
+    ) -> Row<'a, Message> {
+        let indicator = |symbol| {
+            Text::new(symbol)
+                .size(rows_style.row.title.font_size)
+                .vertical_alignment(Vertical::Center)
+        };
+
+        let mut bar = Row::new()
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .align_items(iced_core::Alignment::Center);
+
+        if page.start > 0 {
+            bar = bar.push(indicator("<"));
+        }
+
+        for row in rows {
+            bar = bar.push(row);
+        }
+
+        if page.end < total {
+            bar = bar.push(indicator(">"));
+        }
+
+        bar
+    }
+
+    fn status_bar(&self, style: &'static StatusStyles) -> Container<'_, Message> {
+        let status = StatusLine::new(
+            &self.state,
+            self.current_entries_len(),
+            self.matching_entries_len(),
+        );
+
+        let text = |content: String| Text::new(content).size(style.font_size);
+        let mut bar = Row::new()
+            .spacing(style.spacing)
+            .align_items(iced_core::Alignment::Center)
+            .push(text(status.mode))
+            .push(text(status.results));
+
+        if let Some(backend) = status.backend {
+            bar = bar.push(text(backend.to_string()));
+        }
+
+        if style.key_hints {
+            bar = bar.push(Space::with_width(Length::Fill));
+            for (key, action) in status.hints {
+                bar = bar.push(text(format!("{key} {action}")));
+            }
+        }
+
+        container(bar)
+            .style(iced::theme::Container::Custom(Box::new(style)))
+            .padding(style.padding.to_iced_padding())
+            .width(style.width)
+            .height(style.height)
+            .align_y(style.align_y)
+    }
+
+    fn preview_pane(&self, style: &'static PreviewStyles) -> Container<'_, Message> {
+        let mut pane = Column::new().spacing(style.spacing);
+
+        if let Some(preview) = &self.state.preview {
+            let title = container(Text::new(&preview.title).size(style.title.font_size))
+                .style(iced::theme::Container::Custom(Box::new(&style.title)))
+                .padding(style.title.padding.to_iced_padding())
+                .width(style.title.width)
+                .height(style.title.height)
+                .align_x(style.title.align_x)
+                .align_y(style.title.align_y);
+
+            let content: Element<'_, Message> = match &preview.content {
+                PreviewContent::Text(text) => Text::new(text).size(style.font_size).into(),
+                PreviewContent::Image(path) => Image::new(path)
+                    .content_fit(ContentFit::Contain)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+                PreviewContent::Svg(path) => Svg::from_path(path)
+                    .content_fit(ContentFit::Contain)
+                    .width(Length::Fill)
+                    .height(Length::Fill)
+                    .into(),
+                PreviewContent::Details(details) => Column::with_children(
+                    details
+                        .iter()
+                        .map(|(label, value)| {
+                            Column::new()
+                                .push(Text::new(*label).size(style.title.font_size))
+                                .push(Text::new(value).size(style.font_size))
+                                .into()
+                        })
+                        .collect::<Vec<Element<'_, Message>>>(),
+                )
+                .spacing(style.spacing)
+                .into(),
+            };
+
+            pane = pane.push(title).push(content);
+        }
+
+        container(pane)
+            .style(iced::theme::Container::Custom(Box::new(style)))
+            .padding(style.padding.to_iced_padding())
+            .width(style.width)
+            .height(style.height)
+    }
+
+    // Load the preview when the selected entry changes, files are read in the background
+    fn refresh_preview(&mut self) -> Command<Message> {
+        if THEME.preview().is_none() {
+            return Command::none();
+        }
+
+        let source = self.preview_source().unwrap_or(PreviewSource::Ready(None));
+        if self.state.preview_source.as_ref() == Some(&source) {
+            return Command::none();
+        }
+
+        self.state.preview_source = Some(source.clone());
+        match source {
+            PreviewSource::Ready(preview) => {
+                self.state.preview = preview;
+                Command::none()
+            }
+            source => {
+                let loaded = source.clone();
+                Command::perform(async move { loaded.load() }, move |preview| {
+                    Message::PreviewLoaded(source, preview)
+                })
+            }
+        }
+    }
+
+    fn preview_source(&self) -> Option<PreviewSource> {
+        let selected = self.selected();
+        let preview = match self.state.get_active_mode() {
+            ActiveMode::History => {
+                let entry = self.state.cache.de_history().get(selected?)?.path.clone();
+                return Some(PreviewSource::DesktopEntry(entry));
+            }
+            ActiveMode::Web { modifier } => {
+                let definition = WEB_CONFIG.get(modifier)?.first()?;
+                let query = match selected {
+                    None => {
+                        let input = &self.state.input_value.input_display;
+                        input
+                            .strip_prefix(modifier.as_str())
+                            .unwrap_or(input)
+                            .to_string()
+                    }
+                    Some(selected) => self
+                        .state
+                        .cache
+                        .web_history(modifier)
+                        .get(selected)?
+                        .query
+                        .to_string(),
+                };
+
+                Some(Preview::web(query.trim(), definition))
+            }
+            ActiveMode::Themes => {
+                let themes = self.state.matching_themes();
+                let theme = themes.get(selected?)?;
+                return Some(PreviewSource::File(
+                    theme.path.clone(),
+                    Some(theme.name.clone()),
+                ));
+            }
+            ActiveMode::Native { mode_name, .. } => {
+                let entry = self.state.native_search.get(selected?)?;
+                self.state.native_modes.get(mode_name)?.preview(entry)
+            }
+            ActiveMode::Plugin { history: true, .. } => None,
+            ActiveMode::DesktopEntry | ActiveMode::Plugin { .. } => {
+                let entry = self.state.pop_search.get(selected.unwrap_or(0))?;
+                return Some(PreviewSource::PopResult {
+                    name: entry.name.clone(),
+                    description: entry.description.clone(),
+                });
+            }
+        };
+
+        Some(PreviewSource::Ready(preview))
+    }
+
+    // Scroll just enough for the selected line to be visible
     fn snap(&mut self) -> Command<Message> {
-        let total_items = self.current_entries_len() as f32;
-        match self.selected() {
-            None => scrollable::snap_to(SCROLL_ID.clone(), RelativeOffset::START),
-            Some(selected) => {
-                let offset = (1.0 / total_items) * selected as f32;
-                scrollable::snap_to(SCROLL_ID.clone(), RelativeOffset { x: 0.0, y: offset })
+        let offset = match self.selected() {
+            None => Some(0.0),
+            Some(selected) => self.viewport.scroll_target(
+                selected,
+                self.grid_columns(),
+                self.current_entries_len(),
+            ),
+        };
+
+        let Some(offset) = offset else {
+            return Command::none();
+        };
+
+        // Animated scrolling is driven by `Message::Tick`
+        if self
+            .animations
+            .scroll_to(offset, THEME.scrollable().transition_duration)
+        {
+            Command::none()
+        } else {
+            self.scroll_to(offset)
+        }
+    }
+
+    // Programmatic scrolling does not emit `Message::Scrolled`, keep track of it here
+    fn scroll_to(&mut self, offset: f32) -> Command<Message> {
+        self.viewport.on_scrolled(offset);
+        scrollable::scroll_to(SCROLL_ID.clone(), AbsoluteOffset { x: 0.0, y: offset })
+    }
+
+    fn build_rows(
+        &self,
+        range: Range<usize>,
+        rows_style: &'static RowContainerStyle,
+    ) -> Vec<Element<'_, Message>> {
+        let selected = self.selected();
+        let (skip, take) = (range.start, range.len());
+        if let Some(menu) = &self.state.context_menu {
+            return self.context_menu_rows(menu, skip, take, rows_style);
+        }
+
+        match &self.state.get_active_mode() {
+            ActiveMode::Plugin {
+                plugin_name,
+                history,
+                ..
+            } if *history => {
+                let icon = self.state.plugin_matchers.get_plugin_icon(plugin_name);
+                self.state
+                    .cache
+                    .plugin_history(plugin_name)
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(idx, entry)| {
+                        let row = entry.to_row(
+                            self.animations.highlight(selected, idx),
+                            idx,
+                            icon.as_ref(),
+                            rows_style,
+                        );
+                        self.measured(idx, row)
+                    })
+                    .collect()
+            }
+            ActiveMode::Web { modifier, .. } => {
+                let icon = self.state.plugin_matchers.get_plugin_icon("web");
+                self.state
+                    .cache
+                    .web_history(modifier)
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(idx, entry)| {
+                        let row = entry.to_row(
+                            self.animations.highlight(selected, idx),
+                            idx,
+                            icon.as_ref(),
+                            rows_style,
+                        );
+                        self.measured(idx, row)
+                    })
+                    .collect()
+            }
+            ActiveMode::Themes => {
+                let icon = self.state.plugin_matchers.get_plugin_icon(THEMES_MODE);
+                self.state
+                    .matching_themes()
+                    .into_iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(idx, entry)| {
+                        let row = entry.to_row(
+                            self.animations.highlight(selected, idx),
+                            idx,
+                            icon.as_ref(),
+                            rows_style,
+                        );
+                        self.measured(idx, row)
+                    })
+                    .collect()
             }
+            ActiveMode::Native { mode_name, .. } => {
+                let icon = self.state.plugin_matchers.get_plugin_icon(mode_name);
+                self.state
+                    .native_search
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(idx, entry)| {
+                        let row = entry.to_row(
+                            self.animations.highlight(selected, idx),
+                            idx,
+                            icon.as_ref(),
+                            rows_style,
+                        );
+                        self.measured(idx, row)
+                    })
+                    .collect()
+            }
+            ActiveMode::History => {
+                let icon = self
+                    .state
+                    .plugin_matchers
+                    .get_plugin_icon("desktop_entries");
+                self.state
+                    .cache
+                    .de_history()
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .take(take)
+                    .map(|(idx, entry)| {
+                        let row = entry.to_row(
+                            self.animations.highlight(selected, idx),
+                            idx,
+                            icon.as_ref(),
+                            rows_style,
+                        );
+                        self.measured(idx, row)
+                    })
+                    .collect()
+            }
+            _ => self
+                .state
+                .pop_search
+                .iter()
+                .skip(skip)
+                .take(take)
+                .map(|entry| {
+                    let icon = match &THEME.icon_theme {
+                        Some(theme) => entry
+                            .category_icon
+                            .as_ref()
+                            .and_then(|source| IconPath::from_source(source, theme)),
+                        _ => None,
+                    };
+
+                    let idx = entry.id as usize;
+                    let row = PopSearchResult(entry).to_row(
+                        self.animations.highlight(selected, idx),
+                        idx,
+                        icon.as_ref(),
+                        rows_style,
+                    );
+                    self.measured(idx, row)
+                })
+                .collect(),
         }
     }
 
+    fn context_menu_rows<'a>(
+        &'a self,
+        menu: &'a ContextMenu,
+        skip: usize,
+        take: usize,
+        rows_style: &'static RowContainerStyle,
+    ) -> Vec<Element<'a, Message>> {
+        menu.options
+            .iter()
+            .enumerate()
+            .skip(skip)
+            .take(take)
+            .map(|(idx, option)| {
+                let icon = match (&THEME.icon_theme, self.state.get_active_mode()) {
+                    (_, ActiveMode::Native { .. }) => self
+                        .state
+                        .native_search
+                        .get(menu.result as usize)
+                        .and_then(|entry| entry.icon.clone()),
+                    (Some(theme), _) => self
+                        .state
+                        .pop_search
+                        .get(menu.result as usize)
+                        .and_then(|entry| entry.icon.as_ref())
+                        .and_then(|source| IconPath::from_source(source, theme)),
+                    (None, _) => None,
+                };
+
+                let highlight = if idx == menu.selected { 1.0 } else { 0.0 };
+                let row = option.to_row(highlight, idx, icon.as_ref(), rows_style);
+                self.measured(idx, row)
+            })
+            .collect()
+    }
+
+    // Handle mouse interactions on the row and record its height once laid out
+    fn measured<'a>(&'a self, idx: usize, row: Container<'a, Message>) -> Element<'a, Message> {
+        let row = mouse_area(row)
+            .on_right_press(Message::RightClick(idx))
+            .on_middle_press(Message::MiddleClick(idx));
+
+        let row = if THEME.hover_select {
+            row.on_enter(Message::Hover(idx))
+        } else {
+            row
+        };
+
+        Measured::new(row, self.viewport.measured(idx), move |height| {
+            Message::RowMeasured(idx, height)
+        })
+        .into()
+    }
+
     fn on_pop_launcher_message(&mut self, message: SubscriptionMessage) -> Command<Message> {
         match message {
             SubscriptionMessage::Ready(sender) => {
                 self.request_tx = Some(sender);
+                self.state.backend = BackendStatus::Ready;
+            }
+            SubscriptionMessage::Unavailable => {
+                self.state.backend = BackendStatus::Unavailable;
             }
             SubscriptionMessage::PopMessage(response) => match response {
                 Response::Close => exit(0),
-                Response::Context { .. } => todo!("Discrete graphics is not implemented"),
+                Response::Context { id, options } => {
+                    if !options.is_empty() {
+                        self.state.context_menu = Some(ContextMenu {
+                            result: id,
+                            options,
+                            selected: 0,
+                        });
+                        return self.on_results_replaced();
+                    }
+                }
                 Response::DesktopEntry { path, .. } => {
                     debug!("Launch DesktopEntry {path:?} via run_command");
                     let _ = self.run_command(path);
                 }
                 Response::Update(search_updates) => {
+                    self.state.backend = BackendStatus::Ready;
                     if self.state.exec_on_next_search {
                         debug!("Launch entry 0 via PopRequest::Activate");
                         self.pop_request(Request::Activate(0))
@@ -464,6 +1436,11 @@ impl Onagre<'_> {
                         return Command::none();
                     }
                     self.state.pop_search = search_updates;
+                    self.state.context_menu = None;
+                    let estimate = THEME
+                        .rows(self.state.get_active_mode())
+                        .estimated_row_height();
+                    self.viewport.reset(estimate);
                 }
                 Response::Fill(fill) => self.complete(fill),
             },
@@ -472,6 +1449,70 @@ impl Onagre<'_> {
         Command::none()
     }
 
+    fn activate_native(&mut self) -> Command<Message> {
+        let ActiveMode::Native { mode_name, .. } = &self.state.input_value.mode else {
+            return Command::none();
+        };
+
+        let query = self.state.mode_query().to_string();
+        let native_search = &self.state.native_search;
+        let entry = self.selected().and_then(|idx| native_search.get(idx));
+        let Some(mode) = self.state.native_modes.get_mut(mode_name) else {
+            return Command::none();
+        };
+
+        let activation = mode.activate(&query, entry);
+        let recorded = !matches!(activation, Activation::Fill(_) | Activation::None);
+        if mode.history() && recorded {
+            let key = entry.map(|entry| mode.history_key(entry)).unwrap_or(query);
+            if !key.is_empty() {
+                PluginCommandEntity::persist(mode_name, &key, &self.state.cache.db);
+            }
+        }
+
+        debug!("Native mode {mode_name} activation: {activation:?}");
+        self.run_activation(activation)
+    }
+
+    fn run_activation(&mut self, activation: Activation) -> Command<Message> {
+        match activation {
+            Activation::Exec(command) => {
+                let args = shell_words::split(&command).unwrap_or_default();
+                if let Some((program, args)) = args.split_first() {
+                    if let Err(err) = std::process::Command::new(program).args(args).spawn() {
+                        error!("Failed to run {command}: {err}");
+                    }
+                }
+                exit(0);
+            }
+            Activation::Launch(path) => self.run_command(path),
+            Activation::Copy(text) => {
+                copy_to_clipboard(&text);
+                exit(0);
+            }
+            Activation::CopyImage(path, mime_type) => {
+                copy_image_to_clipboard(&path, mime_type);
+                exit(0);
+            }
+            Activation::Open(target) => {
+                if let Err(err) = std::process::Command::new("xdg-open").arg(&target).spawn() {
+                    error!("Failed to open {target}: {err}");
+                }
+                exit(0);
+            }
+            Activation::Fill(query) => {
+                let modifier = &self.state.input_value.modifier_display;
+                self.complete(format!("{modifier}{query}"));
+                Command::none()
+            }
+            Activation::Exit => exit(0),
+            Activation::Refresh => {
+                Command::batch([self.search_native(), self.on_results_replaced()])
+            }
+            Activation::None => Command::none(),
+        }
+    }
+
     fn complete(&mut self, fill: String) {
         let filled = if THEME.plugin_hint().is_none() {
             self.state.input_value.input_display = fill;
@@ -534,10 +1575,21 @@ impl Onagre<'_> {
                 }
             }
             ActiveMode::History => {
-                let path = self.current_entry();
-                let path = path.unwrap();
-                let _ = self.run_command(path);
+                if let Some(path) = self.current_entry() {
+                    let _ = self.run_command(path);
+                }
             }
+            ActiveMode::Themes => {
+                let themes = self.state.matching_themes();
+                if let Some(theme) = self.selected().and_then(|idx| themes.get(idx)) {
+                    if let Err(err) = themes::persist(&theme.name) {
+                        error!("Failed to persist theme {}: {err}", theme.name);
+                    }
+                }
+
+                exit(0);
+            }
+            ActiveMode::Native { .. } => return self.activate_native(),
             _ => {
                 if self.selected().is_none() {
                     self.pop_request(Request::Activate(0))
@@ -554,7 +1606,18 @@ impl Onagre<'_> {
         Command::none()
     }
 
+    // Number of results, up to `--max-results`
     fn current_entries_len(&self) -> usize {
+        let len = self.matching_entries_len();
+        MAX_RESULTS.get().map_or(len, |max| len.min(*max))
+    }
+
+    // Entries matching the query, including the ones hidden by `--max-results`
+    fn matching_entries_len(&self) -> usize {
+        if let Some(menu) = &self.state.context_menu {
+            return menu.options.len();
+        }
+
         match &self.state.get_active_mode() {
             ActiveMode::Plugin {
                 plugin_name,
@@ -570,11 +1633,51 @@ impl Onagre<'_> {
             ActiveMode::History => self.state.cache.de_len(),
             ActiveMode::DesktopEntry => self.state.pop_search.len(),
             ActiveMode::Web { modifier, .. } => self.state.cache.web_history_len(modifier),
+            ActiveMode::Themes => self.state.matching_themes().len(),
+            ActiveMode::Native { .. } => self.state.native_search.len(),
         }
     }
 
+    fn default_viewport() -> ResultsViewport {
+        let estimate = THEME.app().rows.estimated_row_height();
+        ResultsViewport::new(estimate, THEME.size.1 as f32)
+    }
+
+    // Swap the current theme with the selected one and resize the window accordingly
+    fn preview_theme(&self) -> Command<Message> {
+        let themes = self.state.matching_themes();
+        match self.selected().and_then(|idx| themes.get(idx)) {
+            Some(theme) => {
+                debug!("Previewing theme {}", theme.name);
+                THEME.swap(&theme.path);
+                Onagre::resize_to_theme()
+            }
+            None => Command::none(),
+        }
+    }
+
+    fn resize_to_theme() -> Command<Message> {
+        window::resize(
+            window::Id::MAIN,
+            Size {
+                width: THEME.size.0 as f32,
+                height: THEME.size.1 as f32,
+            },
+        )
+    }
+
     fn pop_request(&self, request: Request) -> Result<(), TrySendError<Request>> {
-        let sender = self.request_tx.as_ref().unwrap();
+        let Some(sender) = self.request_tx.as_ref() else {
+            POP_LAUNCHER_MISSING.call_once(|| {
+                warn!("pop-launcher is not running, only native modes are available")
+            });
+            debug!(
+                "pop-launcher is not running, dropping request {:?}",
+                request
+            );
+            return Ok(());
+        };
+
         let mut sender = sender.clone();
         debug!("Sending message to pop launcher : {:?}", request);
         sender.try_send(request)
@@ -587,47 +1690,70 @@ impl Onagre<'_> {
         }
     }
 
-    fn dec_selected(&mut self) -> Command<Message> {
+    // Move the selection `step` entries backward, in a grid moving up is `columns` entries
+    fn dec_selected(&mut self, step: usize) -> Command<Message> {
+        let previous = self.selected();
         match self.state.selected {
             Selection::Reset => self.state.selected = Selection::Reset,
             Selection::History(selected) => {
-                if selected > 0 {
-                    self.state.selected = Selection::History(selected - 1)
+                if selected >= step {
+                    self.state.selected = Selection::History(selected - step)
                 }
             }
             Selection::PopLauncher(selected) => {
-                if selected > 0 {
-                    self.state.selected = Selection::PopLauncher(selected - 1)
+                if selected >= step {
+                    self.state.selected = Selection::PopLauncher(selected - step)
                 }
             }
         };
 
-        self.snap()
+        self.on_selection_changed(previous)
     }
 
-    fn inc_selected(&mut self) -> Command<Message> {
+    // Move the selection `step` entries forward, in a grid moving down is `columns` entries
+    fn inc_selected(&mut self, step: usize) -> Command<Message> {
+        let previous = self.selected();
         match self.state.selected {
             Selection::Reset => self.state.selected = Selection::History(0),
             Selection::History(selected) => {
                 let total_items = self.current_entries_len();
-                if total_items != 0 && selected < total_items - 1 {
-                    self.state.selected = Selection::History(selected + 1);
+                if selected + step < total_items {
+                    self.state.selected = Selection::History(selected + step);
                 }
             }
             Selection::PopLauncher(selected) => {
                 let total_items = self.current_entries_len();
-                if total_items != 0 && selected < total_items - 1 {
-                    self.state.selected = Selection::PopLauncher(selected + 1);
+                if selected + step < total_items {
+                    self.state.selected = Selection::PopLauncher(selected + step);
                 }
             }
         };
 
-        self.snap()
+        self.on_selection_changed(previous)
+    }
+
+    fn on_selection_changed(&mut self, previous: Option<usize>) -> Command<Message> {
+        Command::batch([self.highlight_selection(previous), self.snap()])
+    }
+
+    // Animate the selection highlight, and preview the selected theme in the theme picker
+    fn highlight_selection(&mut self, previous: Option<usize>) -> Command<Message> {
+        if previous != self.selected() {
+            let rows = THEME.rows(self.state.get_active_mode());
+            self.animations
+                .select(previous, rows.row_selected.transition_duration);
+        }
+
+        match self.state.get_active_mode() {
+            ActiveMode::Themes => self.preview_theme(),
+            _ => Command::none(),
+        }
     }
 
     fn keyboard_event() -> Subscription<Message> {
         event::listen_with(|event, _status| match event {
             Event::Window(_, window::Event::Unfocused) => Some(Message::Unfocused),
+            Event::Window(_, window::Event::Focused) => Some(Message::Focused),
             Event::Keyboard(iced::keyboard::Event::KeyPressed {
                 modifiers: _,
                 text: _,
@@ -2,6 +2,8 @@ use crate::app::plugin_matchers::QueryData;
 use onagre_launcher_toolkit::plugins::web::Config as WebConfig;
 use once_cell::sync::Lazy;
 
+pub(crate) const THEMES_MODE: &str = "themes";
+
 pub(crate) static WEB_CONFIG: Lazy<WebConfig> =
     Lazy::new(onagre_launcher_toolkit::plugins::web::load);
 
@@ -18,6 +20,52 @@ pub enum ActiveMode {
         modifier: String,
         history: bool,
     },
+    // Built-in theme picker, previews the selected theme
+    Themes,
+    // A mode from the `ModeRegistry`, running without pop-launcher
+    Native {
+        mode_name: String,
+        modifier: String,
+    },
+}
+
+impl ActiveMode {
+    // The name used to match `.rows[mode="..."]` style overrides
+    pub fn style_name(&self) -> &str {
+        match self {
+            ActiveMode::History => "history",
+            ActiveMode::DesktopEntry => "desktop_entries",
+            ActiveMode::Web { .. } => "web",
+            ActiveMode::Plugin { plugin_name, .. } => plugin_name,
+            ActiveMode::Themes => THEMES_MODE,
+            ActiveMode::Native { mode_name, .. } => mode_name,
+        }
+    }
+
+    // The name displayed in the status bar
+    pub fn label(&self) -> String {
+        match self {
+            ActiveMode::History => "History".to_string(),
+            ActiveMode::DesktopEntry => "Applications".to_string(),
+            ActiveMode::Web { modifier } => WEB_CONFIG
+                .get(modifier)
+                .and_then(|definitions| definitions.first())
+                .map(|definition| definition.name.clone())
+                .unwrap_or_else(|| "Web".to_string()),
+            ActiveMode::Plugin { plugin_name, .. } => plugin_name.clone(),
+            ActiveMode::Themes => "Themes".to_string(),
+            ActiveMode::Native { mode_name, .. } => mode_name.clone(),
+        }
+    }
+
+    // Whether the results come from pop-launcher rather than from onagre history
+    pub fn shows_pop_results(&self) -> bool {
+        match self {
+            ActiveMode::DesktopEntry => true,
+            ActiveMode::Plugin { history, .. } => !history,
+            _ => false,
+        }
+    }
 }
 
 impl From<QueryData> for ActiveMode {
@@ -27,6 +75,11 @@ impl From<QueryData> for ActiveMode {
             "web" => ActiveMode::Web {
                 modifier: query_data.modifier,
             },
+            THEMES_MODE => ActiveMode::Themes,
+            _ if query_data.native => ActiveMode::Native {
+                mode_name: query_data.plugin_name,
+                modifier: query_data.modifier,
+            },
             _other => ActiveMode::Plugin {
                 plugin_name: query_data.plugin_name,
                 modifier: query_data.modifier,
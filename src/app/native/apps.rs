@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tracing::{debug, warn};
+
+use crate::app::native::{Activation, Mode, NativeEntry};
+use crate::freedesktop::desktop::{split_list, DesktopEntry};
+use crate::icons::IconPath;
+use crate::THEME;
+
+pub const APPS_MODE: &str = "apps";
+
+// An application found in the `applications` data directories
+#[derive(Debug, Clone, PartialEq)]
+pub struct App {
+    // Desktop file ID, e.g. `org.gnome.Nautilus.desktop` or `kde4-kate.desktop`
+    pub id: String,
+    pub path: PathBuf,
+    pub name: String,
+    pub comment: Option<String>,
+    pub icon: Option<String>,
+    pub keywords: Vec<String>,
+    pub categories: Vec<String>,
+}
+
+// Applications indexed by desktop file ID
+#[derive(Debug, Default)]
+pub struct AppIndex {
+    apps: Vec<App>,
+}
+
+impl AppIndex {
+    // `dirs` are ordered by precedence, an entry shadows the ones
+    // with the same desktop file ID in the following directories
+    pub fn build(dirs: &[PathBuf], current_desktops: &[String]) -> Self {
+        let mut seen = HashMap::new();
+        for dir in dirs {
+            for path in desktop_files(dir) {
+                if let Some(id) = desktop_file_id(dir, &path) {
+                    seen.entry(id).or_insert(path);
+                }
+            }
+        }
+
+        let mut apps: Vec<App> = seen
+            .into_iter()
+            .filter_map(|(id, path)| {
+                let entry = DesktopEntry::from_path(&path)?;
+                if !entry.is_displayed(current_desktops) {
+                    return None;
+                }
+
+                let list = |value: &Option<_>| {
+                    value
+                        .as_deref()
+                        .map(|value| split_list(value).map(str::to_string).collect())
+                        .unwrap_or_default()
+                };
+
+                Some(App {
+                    keywords: list(&entry.keywords),
+                    categories: list(&entry.categories),
+                    name: entry.name.to_string(),
+                    comment: entry.comment.map(|comment| comment.to_string()),
+                    icon: entry.icon.map(|icon| icon.to_string()),
+                    id,
+                    path,
+                })
+            })
+            .collect();
+
+        apps.sort_by_key(|app| app.name.to_lowercase());
+        AppIndex { apps }
+    }
+
+    // Matching names first, then keywords, then categories and comments
+    pub fn search(&self, query: &str) -> Vec<&App> {
+        let query = query.trim().to_lowercase();
+        let mut matches: Vec<(u8, &App)> = self
+            .apps
+            .iter()
+            .filter_map(|app| match_score(app, &query).map(|score| (score, app)))
+            .collect();
+
+        // The sort is stable, apps with the same score stay sorted by name
+        matches.sort_by_key(|(score, _)| *score);
+        matches.into_iter().map(|(_, app)| app).collect()
+    }
+}
+
+// Lower is better
+fn match_score(app: &App, query: &str) -> Option<u8> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let name = app.name.to_lowercase();
+    let contains = |values: &[String]| {
+        values
+            .iter()
+            .any(|value| value.to_lowercase().contains(query))
+    };
+
+    if name.starts_with(query) {
+        Some(0)
+    } else if name.split_whitespace().any(|word| word.starts_with(query)) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else if contains(&app.keywords) {
+        Some(3)
+    } else if contains(&app.categories)
+        || app.id.to_lowercase().contains(query)
+        || app
+            .comment
+            .as_ref()
+            .is_some_and(|comment| comment.to_lowercase().contains(query))
+    {
+        Some(4)
+    } else {
+        None
+    }
+}
+
+// `$XDG_DATA_HOME/applications` then `$XDG_DATA_DIRS/applications`
+pub fn application_dirs() -> Vec<PathBuf> {
+    let data_home = std::env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::data_local_dir);
+
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|dirs| !dirs.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    data_home
+        .into_iter()
+        .chain(data_dirs.split(':').map(PathBuf::from))
+        .map(|dir| dir.join("applications"))
+        .collect()
+}
+
+fn current_desktops() -> Vec<String> {
+    std::env::var("XDG_CURRENT_DESKTOP")
+        .map(|desktops| desktops.split(':').map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+fn desktop_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .flat_map(|entry| {
+            let path = entry.path();
+            if path.is_dir() {
+                desktop_files(&path)
+            } else if path.extension().is_some_and(|ext| ext == "desktop") {
+                vec![path]
+            } else {
+                vec![]
+            }
+        })
+        .collect()
+}
+
+// The path relative to the applications directory, with `/` replaced by `-`
+fn desktop_file_id(dir: &Path, path: &Path) -> Option<String> {
+    let relative = path.strip_prefix(dir).ok()?;
+    let parts: Vec<_> = relative
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect();
+
+    Some(parts.join("-"))
+}
+
+// Launches desktop entries when pop-launcher is not available
+#[derive(Debug, Default)]
+pub struct AppsMode {
+    dirs: Vec<PathBuf>,
+    // Built on the first query
+    index: Option<AppIndex>,
+    // Set when the applications directories changed
+    stale: Arc<AtomicBool>,
+    _watcher: Option<RecommendedWatcher>,
+    icons: HashMap<String, Option<IconPath>>,
+}
+
+impl AppsMode {
+    pub fn load() -> Self {
+        let dirs = application_dirs();
+        let stale = Arc::new(AtomicBool::new(false));
+        let watcher = watch(&dirs, Arc::clone(&stale));
+
+        AppsMode {
+            dirs,
+            index: None,
+            stale,
+            _watcher: watcher,
+            icons: HashMap::new(),
+        }
+    }
+
+    fn index(&mut self) -> &AppIndex {
+        if self.stale.swap(false, Ordering::Relaxed) {
+            debug!("Applications changed, refreshing the index");
+            self.index = None;
+        }
+
+        let dirs = &self.dirs;
+        self.index.get_or_insert_with(|| {
+            let index = AppIndex::build(dirs, &current_desktops());
+            debug!("Indexed {} applications", index.apps.len());
+            index
+        })
+    }
+
+    fn icon(icons: &mut HashMap<String, Option<IconPath>>, icon: &str) -> Option<IconPath> {
+        let theme = THEME.icon_theme.as_ref()?;
+        icons
+            .entry(icon.to_string())
+            .or_insert_with(|| {
+                if Path::new(icon).is_absolute() {
+                    IconPath::from_path(icon, false)
+                } else {
+                    IconPath::lookup(icon, theme, THEME.icon_size)
+                }
+            })
+            .clone()
+    }
+}
+
+// Flag the index as stale whenever an applications directory changes
+fn watch(dirs: &[PathBuf], stale: Arc<AtomicBool>) -> Option<RecommendedWatcher> {
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if event.is_ok() {
+            stale.store(true, Ordering::Relaxed);
+        }
+    })
+    .map_err(|err| warn!("Unable to watch applications directories: {err}"))
+    .ok()?;
+
+    for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+        if let Err(err) = watcher.watch(dir, RecursiveMode::Recursive) {
+            warn!("Unable to watch {dir:?}: {err}");
+        }
+    }
+
+    Some(watcher)
+}
+
+impl Mode for AppsMode {
+    fn name(&self) -> &str {
+        APPS_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "apps "
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("applications-other")
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let apps: Vec<App> = self.index().search(query).into_iter().cloned().collect();
+        apps.into_iter()
+            .map(|app| NativeEntry {
+                icon: app
+                    .icon
+                    .as_deref()
+                    .and_then(|icon| Self::icon(&mut self.icons, icon)),
+                name: app.name,
+                description: app.comment,
+                data: app.path.to_string_lossy().to_string(),
+                glyph: None,
+            })
+            .collect()
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        match entry {
+            Some(entry) => Activation::Launch(PathBuf::from(&entry.data)),
+            None => Activation::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::apps::AppIndex;
+    use speculoos::prelude::*;
+    use std::path::{Path, PathBuf};
+    use tempfile::TempDir;
+
+    fn desktop_file(dir: &Path, name: &str, content: &str) {
+        std::fs::create_dir_all(dir.join(name).parent().unwrap()).unwrap();
+        std::fs::write(
+            dir.join(name),
+            format!("[Desktop Entry]\nType=Application\nExec=true\n{content}"),
+        )
+        .unwrap();
+    }
+
+    fn data_dirs(root: &TempDir) -> (PathBuf, PathBuf) {
+        (root.path().join("home"), root.path().join("system"))
+    }
+
+    #[test]
+    fn should_shadow_entries_by_desktop_file_id() {
+        let root = tempfile::tempdir().unwrap();
+        let (home, system) = data_dirs(&root);
+        desktop_file(&home, "editor.desktop", "Name=My Editor\n");
+        desktop_file(&system, "editor.desktop", "Name=Editor\n");
+        desktop_file(&home, "removed.desktop", "Name=Removed\nHidden=true\n");
+        desktop_file(&system, "removed.desktop", "Name=Removed\n");
+        desktop_file(&system, "kde4/kate.desktop", "Name=Kate\n");
+        desktop_file(
+            &system,
+            "mime.desktop",
+            "Name=Mime handler\nNoDisplay=true\n",
+        );
+
+        let index = AppIndex::build(&[home, system], &[]);
+        let apps = index.search("");
+        let ids: Vec<&str> = apps.iter().map(|app| app.id.as_str()).collect();
+        let names: Vec<&str> = apps.iter().map(|app| app.name.as_str()).collect();
+
+        assert_that!(ids).is_equal_to(vec!["kde4-kate.desktop", "editor.desktop"]);
+        assert_that!(names).is_equal_to(vec!["Kate", "My Editor"]);
+    }
+
+    #[test]
+    fn should_rank_name_before_keywords_and_categories() {
+        let root = tempfile::tempdir().unwrap();
+        let (home, system) = data_dirs(&root);
+        desktop_file(
+            &system,
+            "files.desktop",
+            "Name=Files\nKeywords=folder;explorer;\n",
+        );
+        desktop_file(
+            &system,
+            "term.desktop",
+            "Name=Terminal\nCategories=System;Utility;\n",
+        );
+        desktop_file(&system, "explorer.desktop", "Name=Web Explorer\n");
+        desktop_file(&system, "kde.desktop", "Name=Konsole\nOnlyShowIn=KDE;\n");
+
+        let index = AppIndex::build(&[home, system], &["GNOME".to_string()]);
+        let names = |query| {
+            index
+                .search(query)
+                .iter()
+                .map(|app| app.name.clone())
+                .collect::<Vec<String>>()
+        };
+
+        assert_that!(names("expl"))
+            .is_equal_to(vec!["Web Explorer".to_string(), "Files".to_string()]);
+        assert_that!(names("utility")).is_equal_to(vec!["Terminal".to_string()]);
+        assert_that!(names("konsole")).is_empty();
+    }
+}
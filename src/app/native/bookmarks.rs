@@ -0,0 +1,485 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+
+use rusqlite::{Connection, OpenFlags};
+use serde_json::Value;
+use tracing::{debug, error};
+
+use crate::app::native::{Activation, Loading, Mode, NativeEntry};
+
+pub const BOOKMARKS_MODE: &str = "bookmarks";
+
+// Profile directories holding a `places.sqlite` database, relative to the home directory
+const FIREFOX_PROFILES: [&str; 4] = [
+    ".mozilla/firefox/*/places.sqlite",
+    ".librewolf/*/places.sqlite",
+    ".var/app/org.mozilla.firefox/.mozilla/firefox/*/places.sqlite",
+    "snap/firefox/common/.mozilla/firefox/*/places.sqlite",
+];
+
+// Profile directories holding a `Bookmarks` file, relative to the config directory
+const CHROMIUM_PROFILES: [&str; 6] = [
+    "chromium/*/Bookmarks",
+    "google-chrome/*/Bookmarks",
+    "BraveSoftware/Brave-Browser/*/Bookmarks",
+    "vivaldi/*/Bookmarks",
+    "microsoft-edge/*/Bookmarks",
+    "thorium/*/Bookmarks",
+];
+
+// Bookmark folders, with their parent folder
+const FIREFOX_FOLDERS: &str = "SELECT id, parent, title FROM moz_bookmarks WHERE type = 2";
+
+// Bookmarked urls, `place:` urls are Firefox smart folders
+const FIREFOX_BOOKMARKS: &str = "SELECT b.title, p.url, b.parent \
+     FROM moz_bookmarks b JOIN moz_places p ON b.fk = p.id \
+     WHERE b.type = 1 AND p.url NOT LIKE 'place:%' \
+     ORDER BY b.parent, b.position";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+    // Folder names from the root, separated by `/`
+    pub folder: String,
+}
+
+impl Bookmark {
+    fn matches(&self, words: &[&str]) -> bool {
+        let text = format!("{} {} {}", self.title, self.url, self.folder).to_lowercase();
+        words.iter().all(|word| text.contains(word))
+    }
+}
+
+fn glob_paths(root: &Path, patterns: &[&str]) -> Vec<PathBuf> {
+    patterns
+        .iter()
+        .filter_map(|pattern| glob::glob(&root.join(pattern).to_string_lossy()).ok())
+        .flat_map(|paths| paths.flatten())
+        .collect()
+}
+
+// Bookmarks of a Firefox `places.sqlite` database, read from a copy as Firefox locks it
+pub fn read_firefox(places: &Path) -> anyhow::Result<Vec<Bookmark>> {
+    let dir = tempfile::Builder::new().prefix("onagre-places").tempdir()?;
+    let copy = dir.path().join("places.sqlite");
+    let mut has_wal = false;
+    for suffix in ["", "-wal"] {
+        let source = PathBuf::from(format!("{}{suffix}", places.display()));
+        let target = PathBuf::from(format!("{}{suffix}", copy.display()));
+        if source.exists() {
+            match copy_private(&source, &target) {
+                Ok(()) => has_wal |= !suffix.is_empty(),
+                Err(err) => debug!("Unable to copy {source:?}: {err}"),
+            }
+        }
+    }
+
+    // The copied write ahead log can only be applied with write access
+    let flags = if has_wal {
+        OpenFlags::SQLITE_OPEN_READ_WRITE
+    } else {
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+    };
+
+    Ok(query_firefox(&copy, flags)?)
+}
+
+// Copy `source` to a new file only readable by the current user
+fn copy_private(source: &Path, target: &Path) -> std::io::Result<()> {
+    let mut target = OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(target)?;
+    std::io::copy(&mut File::open(source)?, &mut target)?;
+    Ok(())
+}
+
+fn query_firefox(places: &Path, flags: OpenFlags) -> rusqlite::Result<Vec<Bookmark>> {
+    let connection = Connection::open_with_flags(places, flags)?;
+
+    let mut statement = connection.prepare(FIREFOX_FOLDERS)?;
+    let folders: HashMap<i64, (i64, String)> = statement
+        .query_map([], |row| {
+            let title: Option<String> = row.get(2)?;
+            Ok((row.get(0)?, (row.get(1)?, title.unwrap_or_default())))
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    let mut statement = connection.prepare(FIREFOX_BOOKMARKS)?;
+    let bookmarks = statement
+        .query_map([], |row| {
+            let title: Option<String> = row.get(0)?;
+            Ok(Bookmark {
+                title: title.unwrap_or_default(),
+                url: row.get(1)?,
+                folder: firefox_folder(&folders, row.get(2)?),
+            })
+        })?
+        .collect::<rusqlite::Result<_>>()?;
+
+    Ok(bookmarks)
+}
+
+// The root folders have a parent but no title
+fn firefox_folder(folders: &HashMap<i64, (i64, String)>, mut id: i64) -> String {
+    let mut names = vec![];
+    let mut visited = HashSet::new();
+    while let Some((parent, title)) = folders.get(&id) {
+        if !visited.insert(id) {
+            break;
+        }
+
+        if !title.is_empty() {
+            names.push(title.as_str());
+        }
+
+        id = *parent;
+    }
+
+    names.reverse();
+    names.join("/")
+}
+
+// Bookmarks of a Chromium `Bookmarks` file
+pub fn read_chromium(content: &str) -> serde_json::Result<Vec<Bookmark>> {
+    let bookmarks: Value = serde_json::from_str(content)?;
+    let mut entries = vec![];
+    if let Some(roots) = bookmarks.get("roots").and_then(Value::as_object) {
+        for root in roots.values() {
+            chromium_node(root, &[], &mut entries);
+        }
+    }
+
+    Ok(entries)
+}
+
+fn chromium_node(node: &Value, folders: &[&str], bookmarks: &mut Vec<Bookmark>) {
+    let name = node.get("name").and_then(Value::as_str).unwrap_or_default();
+    match node.get("type").and_then(Value::as_str) {
+        Some("url") => {
+            if let Some(url) = node.get("url").and_then(Value::as_str) {
+                bookmarks.push(Bookmark {
+                    title: name.to_string(),
+                    url: url.to_string(),
+                    folder: folders.join("/"),
+                });
+            }
+        }
+        Some("folder") => {
+            let mut path = folders.to_vec();
+            path.push(name);
+            for child in node
+                .get("children")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                chromium_node(child, &path, bookmarks);
+            }
+        }
+        _ => {}
+    }
+}
+
+// Bookmarks of the local Firefox and Chromium based browser profiles,
+// opened with the default browser
+#[derive(Debug, Default)]
+pub struct BookmarksMode {
+    firefox: Vec<PathBuf>,
+    chromium: Vec<PathBuf>,
+    // Read in the background on the first query
+    bookmarks: Option<Vec<Bookmark>>,
+    loading: bool,
+}
+
+impl BookmarksMode {
+    pub fn load() -> Self {
+        let firefox = dirs::home_dir()
+            .map(|home| glob_paths(&home, &FIREFOX_PROFILES))
+            .unwrap_or_default();
+        let chromium = dirs::config_dir()
+            .map(|config| glob_paths(&config, &CHROMIUM_PROFILES))
+            .unwrap_or_default();
+
+        BookmarksMode::new(firefox, chromium)
+    }
+
+    pub fn new(firefox: Vec<PathBuf>, chromium: Vec<PathBuf>) -> Self {
+        BookmarksMode {
+            firefox,
+            chromium,
+            bookmarks: None,
+            loading: false,
+        }
+    }
+}
+
+fn read_bookmarks(firefox: &[PathBuf], chromium: &[PathBuf]) -> Vec<Bookmark> {
+    let mut bookmarks = vec![];
+    for places in firefox {
+        match read_firefox(places) {
+            Ok(entries) => bookmarks.extend(entries),
+            Err(err) => error!("Unable to read Firefox bookmarks {places:?}: {err}"),
+        }
+    }
+
+    for path in chromium {
+        let entries = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|content| read_chromium(&content).map_err(|err| err.to_string()));
+        match entries {
+            Ok(entries) => bookmarks.extend(entries),
+            Err(err) => error!("Unable to read Chromium bookmarks {path:?}: {err}"),
+        }
+    }
+
+    // The same url bookmarked in several browsers
+    let mut urls = HashSet::new();
+    bookmarks.retain(|bookmark| urls.insert(bookmark.url.clone()));
+    bookmarks
+}
+
+fn bookmark_entry(bookmark: &Bookmark) -> NativeEntry {
+    let name = if bookmark.title.is_empty() {
+        bookmark.url.clone()
+    } else {
+        bookmark.title.clone()
+    };
+
+    let description = if bookmark.folder.is_empty() {
+        bookmark.url.clone()
+    } else {
+        format!("{} · {}", bookmark.folder, bookmark.url)
+    };
+
+    NativeEntry {
+        name,
+        description: Some(description),
+        icon: None,
+        glyph: None,
+        data: bookmark.url.clone(),
+    }
+}
+
+impl Mode for BookmarksMode {
+    fn name(&self) -> &str {
+        BOOKMARKS_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "bm "
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("bookmarks")
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let query = query.to_lowercase();
+        let words: Vec<&str> = query.split_whitespace().collect();
+        let Some(bookmarks) = &self.bookmarks else {
+            return vec![];
+        };
+
+        let mut matches: Vec<&Bookmark> = bookmarks
+            .iter()
+            .filter(|bookmark| bookmark.matches(&words))
+            .collect();
+
+        // Bookmarks matched by their title first
+        matches.sort_by_key(|bookmark| !bookmark.title.to_lowercase().contains(&query));
+        matches.into_iter().map(bookmark_entry).collect()
+    }
+
+    // Loaded bookmarks are handed back as entries holding their raw title, folder and url
+    fn load(&mut self, _query: &str) -> Option<Loading> {
+        if self.bookmarks.is_some() || self.loading {
+            return None;
+        }
+
+        self.loading = true;
+        let (firefox, chromium) = (self.firefox.clone(), self.chromium.clone());
+        Some(Box::pin(async move {
+            let bookmarks =
+                tokio::task::spawn_blocking(move || read_bookmarks(&firefox, &chromium))
+                    .await
+                    .unwrap_or_else(|err| {
+                        error!("Failed to read bookmarks: {err}");
+                        vec![]
+                    });
+
+            bookmarks
+                .into_iter()
+                .map(|bookmark| NativeEntry {
+                    name: bookmark.title,
+                    description: Some(bookmark.folder),
+                    data: bookmark.url,
+                    ..Default::default()
+                })
+                .collect()
+        }))
+    }
+
+    fn on_loaded(&mut self, _query: &str, entries: Vec<NativeEntry>) {
+        self.loading = false;
+        let bookmarks = entries
+            .into_iter()
+            .map(|entry| Bookmark {
+                title: entry.name,
+                url: entry.data,
+                folder: entry.description.unwrap_or_default(),
+            })
+            .collect();
+
+        self.bookmarks = Some(bookmarks);
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        match entry {
+            Some(entry) => Activation::Open(entry.data.clone()),
+            None => Activation::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::bookmarks::{read_chromium, read_firefox, Bookmark, BookmarksMode};
+    use crate::app::native::{Activation, Mode};
+    use rusqlite::Connection;
+    use speculoos::prelude::*;
+    use std::path::Path;
+
+    fn bookmark(title: &str, url: &str, folder: &str) -> Bookmark {
+        Bookmark {
+            title: title.to_string(),
+            url: url.to_string(),
+            folder: folder.to_string(),
+        }
+    }
+
+    #[test]
+    fn should_read_chromium_bookmarks() {
+        let content = r#"{
+          "roots": {
+            "bookmark_bar": {
+              "name": "Bookmarks bar", "type": "folder",
+              "children": [
+                { "name": "Rust", "type": "url", "url": "https://www.rust-lang.org" },
+                { "name": "Docs", "type": "folder", "children": [
+                  { "name": "iced", "type": "url", "url": "https://docs.rs/iced" }
+                ]}
+              ]
+            }
+          },
+          "version": 1
+        }"#;
+
+        assert_that!(read_chromium(content).unwrap()).is_equal_to(vec![
+            bookmark("Rust", "https://www.rust-lang.org", "Bookmarks bar"),
+            bookmark("iced", "https://docs.rs/iced", "Bookmarks bar/Docs"),
+        ]);
+    }
+
+    fn places_db(places: &Path) -> Connection {
+        let connection = Connection::open(places).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE moz_places (id INTEGER PRIMARY KEY, url TEXT);
+                 CREATE TABLE moz_bookmarks (id INTEGER PRIMARY KEY, type INTEGER, fk INTEGER,
+                     parent INTEGER, position INTEGER, title TEXT);
+                 INSERT INTO moz_places VALUES (1, 'https://onagre.dev'), (2, 'place:sort=8');
+                 INSERT INTO moz_bookmarks VALUES (1, 2, NULL, 0, 0, ''),
+                     (2, 2, NULL, 1, 0, 'toolbar'), (3, 2, NULL, 2, 0, 'Launchers'),
+                     (4, 1, 1, 3, 0, 'Onagre'), (5, 1, 2, 2, 1, 'Most visited');",
+            )
+            .unwrap();
+        connection
+    }
+
+    #[test]
+    fn should_read_firefox_places() {
+        let dir = tempfile::tempdir().unwrap();
+        let places = dir.path().join("places.sqlite");
+        drop(places_db(&places));
+
+        assert_that!(read_firefox(&places).unwrap()).is_equal_to(vec![bookmark(
+            "Onagre",
+            "https://onagre.dev",
+            "toolbar/Launchers",
+        )]);
+    }
+
+    #[test]
+    fn should_read_firefox_write_ahead_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let places = dir.path().join("places.sqlite");
+        let connection = places_db(&places);
+        connection
+            .execute_batch(
+                "PRAGMA journal_mode = WAL; PRAGMA wal_autocheckpoint = 0;
+                 UPDATE moz_bookmarks SET title = 'Onagre launcher' WHERE id = 4;",
+            )
+            .unwrap();
+
+        // Firefox keeps the database open, the update only lives in the log
+        assert_that!(read_firefox(&places).unwrap()).is_equal_to(vec![bookmark(
+            "Onagre launcher",
+            "https://onagre.dev",
+            "toolbar/Launchers",
+        )]);
+    }
+
+    #[tokio::test]
+    async fn should_load_bookmarks_in_background() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Bookmarks");
+        std::fs::write(
+            &path,
+            r#"{ "roots": { "other": { "name": "Other", "type": "folder", "children": [
+              { "name": "Onagre", "type": "url", "url": "https://onagre.dev" }
+            ] } } }"#,
+        )
+        .unwrap();
+
+        let mut mode = BookmarksMode::new(vec![], vec![path]);
+        assert_that!(mode.query("onagre")).is_empty();
+
+        let entries = mode.load("onagre").unwrap().await;
+        assert_that!(mode.load("onagre").is_none()).is_true();
+        mode.on_loaded("onagre", entries);
+
+        assert_that!(mode.bookmarks).is_equal_to(Some(vec![bookmark(
+            "Onagre",
+            "https://onagre.dev",
+            "Other",
+        )]));
+        assert_that!(mode.load("onagre").is_none()).is_true();
+    }
+
+    #[test]
+    fn should_open_matching_bookmarks() {
+        let mut mode = BookmarksMode::new(vec![], vec![]);
+        mode.bookmarks = Some(vec![
+            bookmark("Crates", "https://crates.io/search?q=rust", "Dev"),
+            bookmark(
+                "The Rust book",
+                "https://doc.rust-lang.org/book",
+                "Dev/Rust",
+            ),
+            bookmark("News", "https://news.ycombinator.com", ""),
+        ]);
+
+        let entries = mode.query("rust");
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+
+        assert_that!(names).is_equal_to(vec!["The Rust book", "Crates"]);
+        assert_that!(mode.activate("rust", entries.first())).is_equal_to(Activation::Open(
+            "https://doc.rust-lang.org/book".to_string(),
+        ));
+    }
+}
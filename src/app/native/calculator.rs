@@ -0,0 +1,781 @@
+use std::collections::HashMap;
+
+use thiserror::Error;
+use tracing::error;
+
+use crate::app::native::{Activation, Mode, NativeEntry};
+use crate::db::calculator::AnswerEntity;
+use crate::db::Database;
+
+pub const CALCULATOR_MODE: &str = "calculator";
+
+// Variable bound to the result of the last activated calculation
+const ANSWER: &str = "ans";
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CalcError {
+    #[error("Unexpected end of expression")]
+    UnexpectedEnd,
+    #[error("Unexpected token '{0}'")]
+    UnexpectedToken(String),
+    #[error("Unknown variable or function '{0}'")]
+    Unknown(String),
+    #[error("Cannot convert {0} to {1}")]
+    IncompatibleUnits(&'static str, &'static str),
+    #[error("{0} is not an integer")]
+    NotAnInteger(String),
+    #[error("The result is not a number")]
+    NotANumber,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dimension {
+    Length,
+    Mass,
+    Time,
+    Volume,
+    Data,
+    Temperature,
+}
+
+#[derive(Debug, PartialEq)]
+struct Unit {
+    names: &'static [&'static str],
+    dimension: Dimension,
+    // Value of the unit in the base unit of its dimension
+    factor: f64,
+    // Only used by temperatures, added once scaled to the base unit
+    offset: f64,
+}
+
+const fn unit(names: &'static [&'static str], dimension: Dimension, factor: f64) -> Unit {
+    Unit {
+        names,
+        dimension,
+        factor,
+        offset: 0.0,
+    }
+}
+
+static UNITS: [Unit; 33] = [
+    unit(
+        &["mm", "millimeter", "millimeters"],
+        Dimension::Length,
+        0.001,
+    ),
+    unit(
+        &["cm", "centimeter", "centimeters"],
+        Dimension::Length,
+        0.01,
+    ),
+    unit(&["m", "meter", "meters"], Dimension::Length, 1.0),
+    unit(
+        &["km", "kilometer", "kilometers"],
+        Dimension::Length,
+        1000.0,
+    ),
+    unit(&["in", "inch", "inches"], Dimension::Length, 0.0254),
+    unit(&["ft", "foot", "feet"], Dimension::Length, 0.3048),
+    unit(&["yd", "yard", "yards"], Dimension::Length, 0.9144),
+    unit(&["mi", "mile", "miles"], Dimension::Length, 1609.344),
+    unit(&["mg", "milligram", "milligrams"], Dimension::Mass, 0.001),
+    unit(&["g", "gram", "grams"], Dimension::Mass, 1.0),
+    unit(&["kg", "kilogram", "kilograms"], Dimension::Mass, 1000.0),
+    unit(&["t", "tonne", "tonnes"], Dimension::Mass, 1_000_000.0),
+    unit(&["oz", "ounce", "ounces"], Dimension::Mass, 28.349_523_125),
+    unit(
+        &["lb", "lbs", "pound", "pounds"],
+        Dimension::Mass,
+        453.592_37,
+    ),
+    unit(
+        &["ms", "millisecond", "milliseconds"],
+        Dimension::Time,
+        0.001,
+    ),
+    unit(&["s", "sec", "second", "seconds"], Dimension::Time, 1.0),
+    unit(&["min", "minute", "minutes"], Dimension::Time, 60.0),
+    unit(&["h", "hour", "hours"], Dimension::Time, 3600.0),
+    unit(&["d", "day", "days"], Dimension::Time, 86400.0),
+    unit(
+        &["ml", "milliliter", "milliliters"],
+        Dimension::Volume,
+        0.001,
+    ),
+    unit(&["l", "liter", "liters"], Dimension::Volume, 1.0),
+    unit(
+        &["gal", "gallon", "gallons"],
+        Dimension::Volume,
+        3.785_411_784,
+    ),
+    unit(&["B", "byte", "bytes"], Dimension::Data, 1.0),
+    unit(&["KB", "kB"], Dimension::Data, 1e3),
+    unit(&["MB"], Dimension::Data, 1e6),
+    unit(&["GB"], Dimension::Data, 1e9),
+    unit(&["TB"], Dimension::Data, 1e12),
+    unit(&["KiB"], Dimension::Data, 1024.0),
+    unit(&["MiB"], Dimension::Data, 1_048_576.0),
+    unit(&["GiB"], Dimension::Data, 1_073_741_824.0),
+    unit(&["K", "kelvin"], Dimension::Temperature, 1.0),
+    Unit {
+        names: &["C", "°C", "celsius"],
+        dimension: Dimension::Temperature,
+        factor: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        names: &["F", "°F", "fahrenheit"],
+        dimension: Dimension::Temperature,
+        factor: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+    },
+];
+
+fn find_unit(name: &str) -> Option<&'static Unit> {
+    UNITS.iter().find(|unit| unit.names.contains(&name))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Ident(String),
+    Op(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, CalcError> {
+    let mut tokens = vec![];
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c.is_ascii_digit() || c == '.' {
+            let mut literal = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '_' {
+                    literal.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.extend(parse_number(&literal)?);
+        } else if c.is_alphabetic() || c == '_' || c == '°' {
+            let mut ident = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_alphanumeric() || c == '_' || c == '°' {
+                    ident.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            tokens.push(Token::Ident(ident));
+        } else if "+-*/^%()=".contains(c) {
+            tokens.push(Token::Op(c));
+            chars.next();
+        } else {
+            return Err(CalcError::UnexpectedToken(c.to_string()));
+        }
+    }
+
+    Ok(tokens)
+}
+
+// A number literal, possibly followed by a unit without a space (`10km`)
+fn parse_number(literal: &str) -> Result<Vec<Token>, CalcError> {
+    let radix = match literal.get(..2) {
+        Some("0x") => Some(16),
+        Some("0b") => Some(2),
+        Some("0o") => Some(8),
+        _ => None,
+    };
+
+    if let Some(radix) = radix {
+        let digits = literal[2..].replace('_', "");
+        return i64::from_str_radix(&digits, radix)
+            .map(|value| vec![Token::Number(value as f64)])
+            .map_err(|_| CalcError::UnexpectedToken(literal.to_string()));
+    }
+
+    let split = literal
+        .find(|c: char| c.is_alphabetic())
+        .unwrap_or(literal.len());
+    let (number, unit) = literal.split_at(split);
+    let number = number
+        .replace('_', "")
+        .parse::<f64>()
+        .map_err(|_| CalcError::UnexpectedToken(literal.to_string()))?;
+
+    let mut tokens = vec![Token::Number(number)];
+    if !unit.is_empty() {
+        tokens.push(Token::Ident(unit.to_string()));
+    }
+
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Quantity {
+    value: f64,
+    unit: Option<&'static Unit>,
+    // `10%` is kept apart so `200 + 10%` means 220
+    percent: bool,
+}
+
+impl Quantity {
+    fn number(value: f64) -> Self {
+        Quantity {
+            value,
+            unit: None,
+            percent: false,
+        }
+    }
+
+    fn ratio(&self) -> f64 {
+        if self.percent {
+            self.value / 100.0
+        } else {
+            self.value
+        }
+    }
+
+    fn convert(&self, target: &'static Unit) -> Result<Quantity, CalcError> {
+        let unit = match self.unit {
+            None => target,
+            Some(unit) if unit.dimension == target.dimension => unit,
+            Some(unit) => return Err(CalcError::IncompatibleUnits(unit.names[0], target.names[0])),
+        };
+
+        let base = self.value * unit.factor + unit.offset;
+        Ok(Quantity {
+            value: (base - target.offset) / target.factor,
+            unit: Some(target),
+            percent: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Decimal,
+    Hex,
+    Binary,
+    Octal,
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    variables: &'a HashMap<String, f64>,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn eat_op(&mut self, op: char) -> bool {
+        if self.peek() == Some(&Token::Op(op)) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_ident(&mut self, names: &[&str]) -> bool {
+        match self.peek() {
+            Some(Token::Ident(ident)) if names.contains(&ident.as_str()) => {
+                self.pos += 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // conversion := additive [("to" | "in") (unit | "hex" | "bin" | "oct" | "dec")]
+    fn conversion(&mut self) -> Result<(Quantity, Format), CalcError> {
+        let quantity = self.additive()?;
+        if !self.eat_ident(&["to", "in", "as"]) {
+            return Ok((quantity, Format::Decimal));
+        }
+
+        match self.next() {
+            Some(Token::Ident(target)) => match target.as_str() {
+                "hex" => Ok((quantity, Format::Hex)),
+                "bin" => Ok((quantity, Format::Binary)),
+                "oct" => Ok((quantity, Format::Octal)),
+                "dec" => Ok((quantity, Format::Decimal)),
+                _ => {
+                    let unit = find_unit(&target).ok_or(CalcError::Unknown(target))?;
+                    Ok((quantity.convert(unit)?, Format::Decimal))
+                }
+            },
+            Some(token) => Err(CalcError::UnexpectedToken(format!("{token:?}"))),
+            None => Err(CalcError::UnexpectedEnd),
+        }
+    }
+
+    // additive := multiplicative (("+" | "-") multiplicative)*
+    fn additive(&mut self) -> Result<Quantity, CalcError> {
+        let mut left = self.multiplicative()?;
+        loop {
+            let sign = if self.eat_op('+') {
+                1.0
+            } else if self.eat_op('-') {
+                -1.0
+            } else {
+                return Ok(left);
+            };
+
+            let right = self.multiplicative()?;
+            left = if right.percent {
+                // Adding a percentage of the left operand
+                Quantity {
+                    value: left.value * (1.0 + sign * right.value / 100.0),
+                    ..left
+                }
+            } else {
+                let right = match (left.unit, right.unit) {
+                    (Some(unit), Some(_)) => right.convert(unit)?,
+                    _ => right,
+                };
+                Quantity {
+                    value: left.ratio() + sign * right.value,
+                    unit: left.unit.or(right.unit),
+                    percent: false,
+                }
+            };
+        }
+    }
+
+    // multiplicative := unary (("*" | "/" | "mod" | "of") unary)*
+    fn multiplicative(&mut self) -> Result<Quantity, CalcError> {
+        let mut left = self.unary()?;
+        loop {
+            let op = if self.eat_op('*') || self.eat_ident(&["of"]) {
+                '*'
+            } else if self.eat_op('/') {
+                '/'
+            } else if self.eat_ident(&["mod"]) {
+                '%'
+            } else {
+                return Ok(left);
+            };
+
+            let right = self.unary()?;
+            let (a, b) = (left.ratio(), right.ratio());
+            let value = match op {
+                '*' => a * b,
+                '/' => a / b,
+                _ => a % b,
+            };
+
+            let unit = match (left.unit, right.unit) {
+                // Dividing two lengths gives a ratio
+                (Some(_), Some(_)) if op == '/' => {
+                    let right = right.convert(left.unit.unwrap())?;
+                    left = Quantity::number(left.value / right.value);
+                    continue;
+                }
+                (Some(left), Some(right)) => {
+                    return Err(CalcError::IncompatibleUnits(left.names[0], right.names[0]))
+                }
+                (unit, None) | (None, unit) => unit,
+            };
+
+            left = Quantity {
+                value,
+                unit,
+                percent: false,
+            };
+        }
+    }
+
+    // unary := "-" unary | power
+    fn unary(&mut self) -> Result<Quantity, CalcError> {
+        if self.eat_op('-') {
+            let quantity = self.unary()?;
+            Ok(Quantity {
+                value: -quantity.value,
+                ..quantity
+            })
+        } else if self.eat_op('+') {
+            self.unary()
+        } else {
+            self.power()
+        }
+    }
+
+    // power := postfix ["^" unary]
+    fn power(&mut self) -> Result<Quantity, CalcError> {
+        let base = self.postfix()?;
+        if self.eat_op('^') {
+            let exponent = self.unary()?;
+            Ok(Quantity {
+                value: base.ratio().powf(exponent.ratio()),
+                unit: base.unit,
+                percent: false,
+            })
+        } else {
+            Ok(base)
+        }
+    }
+
+    // postfix := primary ["%"] [unit]
+    fn postfix(&mut self) -> Result<Quantity, CalcError> {
+        let mut quantity = self.primary()?;
+        if self.eat_op('%') {
+            quantity.percent = true;
+        }
+
+        if let Some(Token::Ident(name)) = self.peek() {
+            if let Some(unit) = find_unit(name) {
+                if quantity.unit.is_none() && !self.variables.contains_key(name) {
+                    quantity.unit = Some(unit);
+                    self.pos += 1;
+                }
+            }
+        }
+
+        Ok(quantity)
+    }
+
+    // primary := number | "(" additive ")" | function "(" additive ")" | constant | variable
+    fn primary(&mut self) -> Result<Quantity, CalcError> {
+        match self.next() {
+            Some(Token::Number(value)) => Ok(Quantity::number(value)),
+            Some(Token::Op('(')) => {
+                let quantity = self.additive()?;
+                if self.eat_op(')') {
+                    Ok(quantity)
+                } else {
+                    Err(CalcError::UnexpectedEnd)
+                }
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(value) = self.variables.get(&name) {
+                    return Ok(Quantity::number(*value));
+                }
+
+                match name.as_str() {
+                    "pi" => return Ok(Quantity::number(std::f64::consts::PI)),
+                    "e" => return Ok(Quantity::number(std::f64::consts::E)),
+                    "tau" => return Ok(Quantity::number(std::f64::consts::TAU)),
+                    _ => {}
+                }
+
+                let function = function(&name).ok_or(CalcError::Unknown(name))?;
+                if !self.eat_op('(') {
+                    return Err(CalcError::UnexpectedEnd);
+                }
+
+                let argument = self.additive()?;
+                if !self.eat_op(')') {
+                    return Err(CalcError::UnexpectedEnd);
+                }
+
+                Ok(Quantity::number(function(argument.ratio())))
+            }
+            Some(token) => Err(CalcError::UnexpectedToken(format!("{token:?}"))),
+            None => Err(CalcError::UnexpectedEnd),
+        }
+    }
+}
+
+fn function(name: &str) -> Option<fn(f64) -> f64> {
+    let function: fn(f64) -> f64 = match name {
+        "sqrt" => f64::sqrt,
+        "abs" => f64::abs,
+        "ln" => f64::ln,
+        "log" => f64::log10,
+        "log2" => f64::log2,
+        "exp" => f64::exp,
+        "sin" => f64::sin,
+        "cos" => f64::cos,
+        "tan" => f64::tan,
+        "asin" => f64::asin,
+        "acos" => f64::acos,
+        "atan" => f64::atan,
+        "round" => f64::round,
+        "floor" => f64::floor,
+        "ceil" => f64::ceil,
+        _ => return None,
+    };
+
+    Some(function)
+}
+
+// The result of an expression, and the variable it defines if any
+#[derive(Debug, PartialEq)]
+pub struct Evaluation {
+    pub result: String,
+    pub value: f64,
+    pub assignment: Option<(String, f64)>,
+}
+
+// Evaluate an expression such as `2 ^ 10`, `0xff to bin`, `5 km to mi`,
+// `120 + 15%` or `rate = 1.08`
+pub fn evaluate(
+    expression: &str,
+    variables: &HashMap<String, f64>,
+) -> Result<Evaluation, CalcError> {
+    let mut tokens = tokenize(expression)?;
+    let assigned = match tokens.as_slice() {
+        [Token::Ident(name), Token::Op('='), ..] => Some(name.clone()),
+        _ => None,
+    };
+
+    if assigned.is_some() {
+        tokens.drain(..2);
+    }
+
+    let mut parser = Parser {
+        tokens,
+        pos: 0,
+        variables,
+    };
+
+    let (quantity, format) = parser.conversion()?;
+    if let Some(token) = parser.next() {
+        return Err(CalcError::UnexpectedToken(format!("{token:?}")));
+    }
+
+    let value = quantity.ratio();
+    if !value.is_finite() {
+        return Err(CalcError::NotANumber);
+    }
+
+    let result = match format {
+        Format::Decimal => match quantity.unit {
+            Some(unit) => format!("{} {}", format_number(value), unit.names[0]),
+            None => format_number(value),
+        },
+        Format::Hex => format!("0x{:x}", integer(value)?),
+        Format::Binary => format!("0b{:b}", integer(value)?),
+        Format::Octal => format!("0o{:o}", integer(value)?),
+    };
+
+    Ok(Evaluation {
+        result,
+        value,
+        assignment: assigned.map(|name| (name, value)),
+    })
+}
+
+fn integer(value: f64) -> Result<i64, CalcError> {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Ok(value as i64)
+    } else {
+        Err(CalcError::NotAnInteger(format_number(value)))
+    }
+}
+
+fn format_number(value: f64) -> String {
+    if value == 0.0 {
+        "0".to_string()
+    } else if !(1e-6..1e15).contains(&value.abs()) {
+        format!("{value:e}")
+    } else if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        let formatted = format!("{value:.10}");
+        formatted
+            .trim_end_matches('0')
+            .trim_end_matches('.')
+            .to_string()
+    }
+}
+
+// Copies the result on activation, variables are restored from the history
+// and `ans` holds the last activated result
+#[derive(Debug)]
+pub struct CalculatorMode {
+    db: Database,
+    variables: HashMap<String, f64>,
+    history_loaded: bool,
+}
+
+impl CalculatorMode {
+    pub fn new(db: Database) -> Self {
+        CalculatorMode {
+            db,
+            variables: HashMap::new(),
+            history_loaded: false,
+        }
+    }
+
+    fn entry(&self, expression: &str) -> Option<NativeEntry> {
+        let evaluation = evaluate(expression, &self.variables).ok()?;
+        let name = match &evaluation.assignment {
+            Some((variable, _)) => format!("{variable} = {}", evaluation.result),
+            None => evaluation.result.clone(),
+        };
+
+        Some(NativeEntry {
+            name,
+            description: Some(expression.to_string()),
+            icon: None,
+            glyph: None,
+            data: evaluation.result,
+        })
+    }
+}
+
+impl Mode for CalculatorMode {
+    fn name(&self) -> &str {
+        CALCULATOR_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "="
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("accessories-calculator")
+    }
+
+    fn history(&self) -> bool {
+        true
+    }
+
+    fn load_history(&mut self, keys: &[&str]) {
+        if self.history_loaded {
+            return;
+        }
+
+        // Least used first, so the most used assignment of a variable wins
+        for expression in keys.iter().rev() {
+            if let Ok(Evaluation {
+                assignment: Some((name, value)),
+                ..
+            }) = evaluate(expression, &self.variables)
+            {
+                self.variables.insert(name, value);
+            }
+        }
+
+        if let Some(answer) = AnswerEntity::get(&self.db) {
+            self.variables.insert(ANSWER.to_string(), answer);
+        }
+
+        self.history_loaded = true;
+    }
+
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.description.clone().unwrap_or_default()
+    }
+
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        self.entry(key)
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        self.entry(query.trim()).into_iter().collect()
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        let Some(entry) = entry else {
+            return Activation::None;
+        };
+
+        let expression = entry.description.as_deref().unwrap_or_default();
+        if let Ok(evaluation) = evaluate(expression, &self.variables) {
+            if let Err(err) = AnswerEntity::persist(evaluation.value, &self.db) {
+                error!("Failed to persist the calculator answer: {err}");
+            }
+        }
+
+        Activation::Copy(entry.data.clone())
+    }
+
+    fn complete(&self, entry: &NativeEntry) -> Option<String> {
+        Some(entry.data.clone())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::calculator::{evaluate, CalcError, CalculatorMode};
+    use crate::app::native::{Activation, Mode};
+    use crate::db::Database;
+    use speculoos::prelude::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn calculator(temp: &TempDir) -> CalculatorMode {
+        CalculatorMode::new(Database::open_at(&temp.path().join("db")).unwrap())
+    }
+
+    fn eval(expression: &str) -> String {
+        evaluate(expression, &HashMap::new()).unwrap().result
+    }
+
+    #[test]
+    fn should_evaluate_arithmetic() {
+        assert_that!(eval("1 + 2 * 3")).is_equal_to("7".to_string());
+        assert_that!(eval("(1 + 2) * 3")).is_equal_to("9".to_string());
+        assert_that!(eval("2 ^ 3 ^ 2")).is_equal_to("512".to_string());
+        assert_that!(eval("-2 ^ 2")).is_equal_to("-4".to_string());
+        assert_that!(eval("10 / 4")).is_equal_to("2.5".to_string());
+        assert_that!(eval("sqrt(16) + 7 mod 4")).is_equal_to("7".to_string());
+        assert_that!(evaluate("1 +", &HashMap::new())).is_err();
+    }
+
+    #[test]
+    fn should_evaluate_bases_and_percentages() {
+        assert_that!(eval("0xff + 0b1")).is_equal_to("256".to_string());
+        assert_that!(eval("255 to hex")).is_equal_to("0xff".to_string());
+        assert_that!(eval("0o17 to bin")).is_equal_to("0b1111".to_string());
+        assert_that!(eval("200 + 10%")).is_equal_to("220".to_string());
+        assert_that!(eval("200 - 25%")).is_equal_to("150".to_string());
+        assert_that!(eval("15% of 80")).is_equal_to("12".to_string());
+    }
+
+    #[test]
+    fn should_convert_units() {
+        assert_that!(eval("5 km to m")).is_equal_to("5000 m".to_string());
+        assert_that!(eval("1 m + 20cm")).is_equal_to("1.2 m".to_string());
+        assert_that!(eval("100 C to F")).is_equal_to("212 F".to_string());
+        assert_that!(eval("2 GiB in MiB")).is_equal_to("2048 MiB".to_string());
+        assert_that!(evaluate("1 kg to m", &HashMap::new()))
+            .is_equal_to(Err(CalcError::IncompatibleUnits("kg", "m")));
+    }
+
+    #[test]
+    fn should_restore_variables_history_entry() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut calculator = calculator(&temp);
+        calculator.load_history(&["rate * 2", "rate = 1.5"]);
+
+        let entries = calculator.query("rate * 100");
+
+        assert_that!(entries[0].data).is_equal_to("150".to_string());
+        assert_that!(calculator.history_key(&entries[0])).is_equal_to("rate * 100".to_string());
+    }
+
+    #[test]
+    fn should_bind_ans_to_the_last_activated_result() {
+        let temp = tempfile::tempdir().unwrap();
+        let mut calculator = calculator(&temp);
+        calculator.load_history(&[]);
+        assert_that!(calculator.query("ans * 2")).is_empty();
+
+        let entries = calculator.query("6 * 7");
+        assert_that!(calculator.activate("6 * 7", entries.first()))
+            .is_equal_to(Activation::Copy("42".to_string()));
+
+        let mut calculator = CalculatorMode::new(calculator.db.clone());
+        calculator.load_history(&[]);
+        let entries = calculator.query("ans / 2");
+
+        assert_that!(entries[0].data).is_equal_to("21".to_string());
+    }
+}
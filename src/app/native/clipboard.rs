@@ -0,0 +1,406 @@
+use std::fs::{DirBuilder, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, error};
+use xxhash_rust::xxh3::xxh3_64;
+
+use crate::app::native::{Activation, Mode, NativeEntry};
+use crate::app::preview::{Preview, PreviewContent};
+use crate::db::clipboard::ClipboardEntity;
+use crate::db::Database;
+use crate::freedesktop::clipboard::is_sensitive;
+
+pub const CLIPBOARD_MODE: &str = "clipboard";
+
+const IMAGE_MIME_TYPE: &str = "image/png";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+// Characters of a text entry shown in its row
+const NAME_LENGTH: usize = 80;
+
+// Content read from the clipboard
+#[derive(Debug, PartialEq)]
+pub enum ClipboardContent {
+    Text(String),
+    Image(Vec<u8>),
+}
+
+impl ClipboardContent {
+    // Text and PNG images are kept, blank text and other binary content are not
+    pub fn parse(content: Vec<u8>) -> Option<Self> {
+        if content.starts_with(PNG_SIGNATURE) {
+            return Some(ClipboardContent::Image(content));
+        }
+
+        String::from_utf8(content)
+            .ok()
+            .filter(|text| !text.trim().is_empty())
+            .map(ClipboardContent::Text)
+    }
+
+    // Keys are stored, the hash must not change across Rust versions
+    fn key(&self) -> String {
+        let hash = match self {
+            ClipboardContent::Text(text) => xxh3_64(text.as_bytes()),
+            ClipboardContent::Image(image) => xxh3_64(image),
+        };
+
+        format!("{hash:016x}")
+    }
+}
+
+fn images_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("onagre-clipboard"))
+}
+
+// Contents copied while the database was not available, one file each
+fn spool_dir() -> Option<PathBuf> {
+    images_dir().map(|dir| dir.join("spool"))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+// Password manager contents are never recorded
+fn is_ignored(mime_types: &[String]) -> bool {
+    // Set by `wl-paste --watch`, 'sensitive' when a password manager hint is offered
+    let state = std::env::var("CLIPBOARD_STATE").unwrap_or_default();
+    let ignored =
+        is_sensitive(mime_types) || matches!(state.as_str(), "sensitive" | "clear" | "nil");
+    if ignored {
+        debug!("Ignoring clipboard content, state: '{state}'");
+    }
+
+    ignored
+}
+
+// Record the clipboard content in the history, unless it comes from a password manager
+pub fn store(content: Vec<u8>, mime_types: &[String], db: &Database) -> anyhow::Result<()> {
+    if is_ignored(mime_types) {
+        return Ok(());
+    }
+
+    record(content, now(), db)
+}
+
+// Keep the clipboard content until the database can be opened, see `drain_spool`
+pub fn spool(content: Vec<u8>, mime_types: &[String]) -> anyhow::Result<()> {
+    if is_ignored(mime_types) {
+        return Ok(());
+    }
+
+    let dir = spool_dir().ok_or_else(|| anyhow::anyhow!("No data directory"))?;
+    spool_in(&dir, &content, now())
+}
+
+fn spool_in(dir: &Path, content: &[u8], copied_at: u64) -> anyhow::Result<()> {
+    DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    // The copy time orders the spooled contents
+    let path = dir.join(format!("{copied_at:020}-{}", std::process::id()));
+    OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .mode(0o600)
+        .open(path)?
+        .write_all(content)?;
+    Ok(())
+}
+
+// Record the spooled clipboard contents, oldest first
+pub fn drain_spool(db: &Database) {
+    if let Some(dir) = spool_dir() {
+        drain_spool_in(&dir, db);
+    }
+}
+
+fn drain_spool_in(dir: &Path, db: &Database) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    paths.sort();
+
+    for path in paths {
+        let copied_at = path
+            .file_name()
+            .and_then(|name| name.to_str()?.split('-').next()?.parse().ok())
+            .unwrap_or_else(now);
+
+        let recorded = std::fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|content| record(content, copied_at, db));
+        if let Err(err) = recorded {
+            error!("Unable to record spooled clipboard content {path:?}: {err}");
+        }
+
+        if let Err(err) = std::fs::remove_file(&path) {
+            error!("Unable to remove spooled clipboard content {path:?}: {err}");
+        }
+    }
+}
+
+fn record(content: Vec<u8>, copied_at: u64, db: &Database) -> anyhow::Result<()> {
+    let Some(content) = ClipboardContent::parse(content) else {
+        return Ok(());
+    };
+
+    let key = content.key();
+    let (content, image) = match content {
+        ClipboardContent::Text(text) => (text, false),
+        ClipboardContent::Image(image) => {
+            let dir = images_dir().ok_or_else(|| anyhow::anyhow!("No data directory"))?;
+            std::fs::create_dir_all(&dir)?;
+            let path = dir.join(format!("{key}.png"));
+            std::fs::write(&path, image)?;
+            (path.to_string_lossy().to_string(), true)
+        }
+    };
+
+    ClipboardEntity {
+        key: key.into(),
+        content: content.into(),
+        image,
+        pinned: false,
+        copied_at,
+    }
+    .persist(db)?;
+
+    Ok(())
+}
+
+// The first non blank line of the text, shortened
+fn entry_name(entity: &ClipboardEntity) -> String {
+    if entity.image {
+        return "Image".to_string();
+    }
+
+    let line = entity
+        .content
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .unwrap_or_default();
+
+    match line.char_indices().nth(NAME_LENGTH) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+fn entry_description(entity: &ClipboardEntity) -> Option<String> {
+    let lines = Some(entity.content.lines().count())
+        .filter(|lines| !entity.image && *lines > 1)
+        .map(|lines| format!("{lines} lines"));
+    let pinned = entity.pinned.then(|| "Pinned".to_string());
+
+    let description: Vec<String> = pinned.into_iter().chain(lines).collect();
+    (!description.is_empty()).then(|| description.join(" · "))
+}
+
+// Copied text and images, most recent first, pinned entries are listed on top
+#[derive(Debug)]
+pub struct ClipboardMode {
+    db: Database,
+    // Entries of the last query
+    entries: Vec<ClipboardEntity<'static>>,
+}
+
+impl ClipboardMode {
+    pub fn new(db: Database) -> Self {
+        ClipboardMode {
+            db,
+            entries: vec![],
+        }
+    }
+
+    fn entity(&self, entry: &NativeEntry) -> Option<&ClipboardEntity<'static>> {
+        self.entries.iter().find(|entity| entity.key == entry.data)
+    }
+}
+
+impl Mode for ClipboardMode {
+    fn name(&self) -> &str {
+        CLIPBOARD_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "clip "
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("edit-paste")
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let query = query.to_lowercase();
+        // Contents copied while onagre had the database open
+        drain_spool(&self.db);
+        self.entries = ClipboardEntity::all(&self.db);
+        self.entries
+            .iter()
+            .filter(|entity| {
+                if entity.image {
+                    "image".contains(&query)
+                } else {
+                    entity.content.to_lowercase().contains(&query)
+                }
+            })
+            .map(|entity| NativeEntry {
+                name: entry_name(entity),
+                description: entry_description(entity),
+                icon: None,
+                glyph: None,
+                data: entity.key.to_string(),
+            })
+            .collect()
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        match entry.and_then(|entry| self.entity(entry)) {
+            Some(entity) if entity.image => {
+                Activation::CopyImage(PathBuf::from(entity.content.as_ref()), IMAGE_MIME_TYPE)
+            }
+            Some(entity) => Activation::Copy(entity.content.to_string()),
+            None => Activation::None,
+        }
+    }
+
+    fn complete(&self, _entry: &NativeEntry) -> Option<String> {
+        None
+    }
+
+    fn preview(&self, entry: &NativeEntry) -> Option<Preview> {
+        let entity = self.entity(entry)?;
+        let content = if entity.image {
+            PreviewContent::Image(PathBuf::from(entity.content.as_ref()))
+        } else {
+            PreviewContent::Text(entity.content.to_string())
+        };
+
+        let kind = if entity.image { "Image" } else { "Text" };
+        let title = match &entry.description {
+            Some(description) => format!("{kind} · {description}"),
+            None => kind.to_string(),
+        };
+
+        Some(Preview { title, content })
+    }
+
+    fn context(&self, entry: &NativeEntry) -> Vec<String> {
+        let pin = match self.entity(entry) {
+            Some(entity) if entity.pinned => "Unpin",
+            _ => "Pin",
+        };
+
+        vec![pin.to_string(), "Delete".to_string()]
+    }
+
+    fn activate_context(&mut self, entry: &NativeEntry, option: usize) -> Activation {
+        let Some(entity) = self.entity(entry) else {
+            return Activation::None;
+        };
+
+        let result = match option {
+            0 => entity.set_pinned(!entity.pinned, &self.db),
+            _ => entity.delete(&self.db),
+        };
+
+        if let Err(err) = result {
+            error!("Unable to update clipboard entry: {err}");
+        }
+
+        Activation::Refresh
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::clipboard::{drain_spool_in, entry_name, spool_in, ClipboardContent};
+    use crate::db::clipboard::{overflow, ClipboardEntity};
+    use crate::db::Database;
+    use crate::freedesktop::clipboard::is_sensitive;
+    use speculoos::prelude::*;
+
+    fn entity(content: &str, pinned: bool) -> ClipboardEntity<'static> {
+        ClipboardEntity {
+            key: content.to_string().into(),
+            content: content.to_string().into(),
+            image: false,
+            pinned,
+            copied_at: 0,
+        }
+    }
+
+    #[test]
+    fn should_keep_text_and_images() {
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+
+        assert_that!(ClipboardContent::parse(b"hello".to_vec()))
+            .is_equal_to(Some(ClipboardContent::Text("hello".to_string())));
+        assert_that!(ClipboardContent::parse(png.clone()))
+            .is_equal_to(Some(ClipboardContent::Image(png)));
+        assert_that!(ClipboardContent::parse(b" \n".to_vec())).is_none();
+        assert_that!(ClipboardContent::parse(vec![0xff, 0xfe])).is_none();
+    }
+
+    #[test]
+    fn should_keep_keys_stable() {
+        let key = ClipboardContent::Text("hello".to_string()).key();
+
+        assert_that!(key).is_equal_to("9555e8555c62dcfd".to_string());
+    }
+
+    #[test]
+    fn should_record_spooled_contents() {
+        let temp = tempfile::tempdir().unwrap();
+        let spool = temp.path().join("spool");
+        let db = Database::open_at(&temp.path().join("db")).unwrap();
+        spool_in(&spool, b"first", 1).unwrap();
+        spool_in(&spool, b"second", 2).unwrap();
+
+        drain_spool_in(&spool, &db);
+
+        let contents: Vec<(String, u64)> = ClipboardEntity::all(&db)
+            .into_iter()
+            .map(|entity| (entity.content.to_string(), entity.copied_at))
+            .collect();
+        assert_that!(contents)
+            .is_equal_to(vec![("second".to_string(), 2), ("first".to_string(), 1)]);
+        assert_that!(std::fs::read_dir(&spool).unwrap().count()).is_equal_to(0);
+    }
+
+    #[test]
+    fn should_exclude_password_managers() {
+        let keepassxc = ["text/plain", "x-kde-passwordManagerHint"];
+
+        assert_that!(is_sensitive(&keepassxc)).is_true();
+        assert_that!(is_sensitive(&["text/plain", "UTF8_STRING"])).is_false();
+    }
+
+    #[test]
+    fn should_drop_oldest_unpinned_entries() {
+        let entries = vec![
+            entity("pinned", true),
+            entity("newest", false),
+            entity("newer", false),
+            entity("oldest", false),
+        ];
+
+        let dropped: Vec<String> = overflow(entries, 2)
+            .into_iter()
+            .map(|entry| entry.key.to_string())
+            .collect();
+
+        assert_that!(dropped).is_equal_to(vec!["oldest".to_string()]);
+        assert_that!(entry_name(&entity("\n  first line\nsecond", false)))
+            .is_equal_to("first line".to_string());
+    }
+}
@@ -0,0 +1,265 @@
+use emojis::{Emoji, SkinTone};
+use once_cell::sync::Lazy;
+
+use crate::app::native::{Activation, Mode, NativeEntry};
+use crate::freedesktop::keyboard::type_text;
+
+pub const EMOJI_MODE: &str = "emoji";
+
+const MAX_RESULTS: usize = 100;
+
+// Unicode characters are only searched for queries this long, shorter ones match too many names
+const UNICODE_QUERY_LENGTH: usize = 3;
+
+// Query words selecting a skin tone
+const SKIN_TONES: [(&str, SkinTone); 5] = [
+    ("light", SkinTone::Light),
+    ("medium-light", SkinTone::MediumLight),
+    ("medium", SkinTone::Medium),
+    ("medium-dark", SkinTone::MediumDark),
+    ("dark", SkinTone::Dark),
+];
+
+// Names generated from the code point, searching them is pointless
+const GENERATED_NAMES: [&str; 6] = [
+    "CJK UNIFIED IDEOGRAPH-",
+    "CJK COMPATIBILITY IDEOGRAPH-",
+    "HANGUL SYLLABLE ",
+    "TANGUT IDEOGRAPH-",
+    "KHITAN SMALL SCRIPT CHARACTER-",
+    "NUSHU CHARACTER-",
+];
+
+// Named Unicode characters which are not emojis, with their lowercase name
+static UNICODE_NAMES: Lazy<Vec<(char, String)>> = Lazy::new(|| {
+    ('\u{20}'..='\u{3ffff}')
+        .filter(|c| emojis::get(c.encode_utf8(&mut [0; 4])).is_none())
+        .filter_map(|c| {
+            let name = unicode_names2::name(c)?.to_string();
+            let generated = GENERATED_NAMES
+                .iter()
+                .any(|prefix| name.starts_with(prefix));
+            (!generated).then(|| (c, name.to_lowercase()))
+        })
+        .collect()
+});
+
+// Lower is better, `None` when one of the words does not match
+fn rank(name: &str, aliases: &[&str], query: &str, words: &[&str]) -> Option<usize> {
+    let matches =
+        |word: &&str| name.contains(word) || aliases.iter().any(|alias| alias.contains(word));
+    if !words.iter().all(matches) {
+        return None;
+    }
+
+    let rank = if name == query || aliases.contains(&query) {
+        0
+    } else if name.starts_with(query) || aliases.iter().any(|alias| alias.starts_with(query)) {
+        1
+    } else if name.split(' ').any(|word| word.starts_with(query)) {
+        2
+    } else {
+        3
+    };
+
+    Some(rank)
+}
+
+fn emoji_entry(emoji: &Emoji) -> NativeEntry {
+    let shortcodes: Vec<String> = emoji
+        .shortcodes()
+        .map(|shortcode| format!(":{shortcode}:"))
+        .collect();
+
+    NativeEntry {
+        name: emoji.name().to_string(),
+        description: Some(shortcodes.join(" ")).filter(|shortcodes| !shortcodes.is_empty()),
+        icon: None,
+        data: emoji.as_str().to_string(),
+        glyph: Some(emoji.as_str().to_string()),
+    }
+}
+
+fn unicode_entry(c: char, name: &str) -> NativeEntry {
+    NativeEntry {
+        name: name.to_string(),
+        description: Some(format!("U+{:04X}", c as u32)),
+        icon: None,
+        data: c.to_string(),
+        glyph: Some(c.to_string()),
+    }
+}
+
+// Emojis matching all the query words, in the requested skin tone
+pub fn search_emojis(query: &str) -> Vec<NativeEntry> {
+    let query = query.to_lowercase();
+    let mut tone = None;
+    let words: Vec<&str> = query
+        .split_whitespace()
+        .filter(
+            |word| match SKIN_TONES.iter().find(|(name, _)| name == word) {
+                Some((_, skin_tone)) => {
+                    tone = Some(*skin_tone);
+                    false
+                }
+                None => true,
+            },
+        )
+        .collect();
+    let query = words.join(" ");
+
+    let mut matches: Vec<(usize, &Emoji)> = emojis::iter()
+        .filter_map(|emoji| {
+            let aliases: Vec<&str> = emoji.shortcodes().collect();
+            let rank = rank(emoji.name(), &aliases, &query, &words)?;
+            let emoji = tone
+                .and_then(|tone| emoji.with_skin_tone(tone))
+                .unwrap_or(emoji);
+            Some((rank, emoji))
+        })
+        .collect();
+
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, emoji)| emoji_entry(emoji))
+        .collect()
+}
+
+// Named Unicode characters matching all the query words
+pub fn search_unicode(query: &str) -> Vec<NativeEntry> {
+    let query = query.to_lowercase();
+    if query.len() < UNICODE_QUERY_LENGTH {
+        return vec![];
+    }
+
+    let words: Vec<&str> = query.split_whitespace().collect();
+    let mut matches: Vec<(usize, char, &str)> = UNICODE_NAMES
+        .iter()
+        .filter_map(|(c, name)| Some((rank(name, &[], &query, &words)?, *c, name.as_str())))
+        .collect();
+
+    matches.sort_by_key(|(rank, _, _)| *rank);
+    matches
+        .into_iter()
+        .take(MAX_RESULTS)
+        .map(|(_, c, name)| unicode_entry(c, name))
+        .collect()
+}
+
+// Emojis and Unicode characters searched by name or shortcode,
+// a skin tone in the query picks the toned emojis, e.g. `thumbs up dark`
+#[derive(Debug, Default)]
+pub struct EmojiMode;
+
+impl Mode for EmojiMode {
+    fn name(&self) -> &str {
+        EMOJI_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        ":"
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("face-smile")
+    }
+
+    fn history(&self) -> bool {
+        true
+    }
+
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.data.clone()
+    }
+
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        if let Some(emoji) = emojis::get(key) {
+            return Some(emoji_entry(emoji));
+        }
+
+        let mut chars = key.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => {
+                let name = unicode_names2::name(c)?.to_string().to_lowercase();
+                Some(unicode_entry(c, &name))
+            }
+            _ => None,
+        }
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let mut entries = search_emojis(query);
+        if entries.len() < MAX_RESULTS {
+            let remaining = MAX_RESULTS - entries.len();
+            entries.extend(search_unicode(query).into_iter().take(remaining));
+        }
+
+        entries
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        match entry {
+            Some(entry) => Activation::Copy(entry.data.clone()),
+            None => Activation::None,
+        }
+    }
+
+    fn complete(&self, _entry: &NativeEntry) -> Option<String> {
+        None
+    }
+
+    fn context(&self, _entry: &NativeEntry) -> Vec<String> {
+        vec!["Copy".to_string(), "Type".to_string()]
+    }
+
+    fn activate_context(&mut self, entry: &NativeEntry, option: usize) -> Activation {
+        match option {
+            0 => self.activate("", Some(entry)),
+            _ => {
+                type_text(&entry.data);
+                Activation::Exit
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::emoji::{search_emojis, search_unicode, EmojiMode};
+    use crate::app::native::Mode;
+    use speculoos::prelude::*;
+
+    fn first(entries: Vec<crate::app::native::NativeEntry>) -> Option<String> {
+        entries.into_iter().next().map(|entry| entry.data)
+    }
+
+    #[test]
+    fn should_search_emojis_by_name_and_alias() {
+        assert_that!(first(search_emojis("thumbs up"))).is_equal_to(Some("👍".to_string()));
+        assert_that!(first(search_emojis("tada"))).is_equal_to(Some("🎉".to_string()));
+        assert_that!(first(search_emojis("thumbs up dark"))).is_equal_to(Some("👍🏿".to_string()));
+    }
+
+    #[test]
+    fn should_search_unicode_characters() {
+        let entries = search_unicode("em dash");
+
+        assert_that!(entries[0].data).is_equal_to("—".to_string());
+        assert_that!(entries[0].description).is_equal_to(Some("U+2014".to_string()));
+        assert_that!(search_unicode("em")).is_empty();
+    }
+
+    #[test]
+    fn should_restore_history_entries() {
+        let mut mode = EmojiMode;
+
+        let emoji = mode.history_entry("🎉").unwrap();
+        let character = mode.history_entry("—").unwrap();
+
+        assert_that!(emoji.name).is_equal_to("party popper".to_string());
+        assert_that!(emoji.glyph).is_equal_to(Some("🎉".to_string()));
+        assert_that!(character.name).is_equal_to("em dash".to_string());
+    }
+}
@@ -0,0 +1,368 @@
+use std::fmt::Debug;
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+
+use once_cell::unsync::OnceCell;
+use regex::Regex;
+use tracing::{debug, error};
+
+use crate::app::native::apps::AppsMode;
+use crate::app::native::bookmarks::BookmarksMode;
+use crate::app::native::calculator::CalculatorMode;
+use crate::app::native::clipboard::ClipboardMode;
+use crate::app::native::emoji::EmojiMode;
+use crate::app::native::run::RunMode;
+use crate::app::native::script::ScriptMode;
+use crate::app::native::ssh::SshMode;
+use crate::app::native::windows::WindowMode;
+use crate::app::plugin_matchers::Plugin;
+use crate::app::preview::Preview;
+use crate::config::scripts;
+use crate::db::Database;
+use crate::icons::IconPath;
+use crate::THEME;
+
+pub mod apps;
+pub mod bookmarks;
+pub mod calculator;
+pub mod clipboard;
+pub mod emoji;
+pub mod run;
+pub mod script;
+pub mod ssh;
+pub mod windows;
+
+// A result produced by a native mode
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct NativeEntry {
+    pub name: String,
+    pub description: Option<String>,
+    pub icon: Option<IconPath>,
+    // Mode specific value used on activation: a command line, an url ...
+    pub data: String,
+    // Text displayed in place of the icon, e.g. an emoji
+    pub glyph: Option<String>,
+}
+
+// Entries a mode loads off the UI thread, see `Mode::load`
+pub type Loading = Pin<Box<dyn Future<Output = Vec<NativeEntry>> + Send>>;
+
+// What onagre should do once a native entry is activated
+#[derive(Debug, Clone, PartialEq)]
+pub enum Activation {
+    // Run a command line and exit
+    Exec(String),
+    // Launch a desktop entry, recorded in the history, and exit
+    Launch(PathBuf),
+    // Copy some text to the clipboard and exit
+    Copy(String),
+    // Copy an image file with its MIME type to the clipboard and exit
+    CopyImage(PathBuf, &'static str),
+    // Open an url or a path with the default application and exit
+    Open(String),
+    // Replace the query, without the mode prefix
+    Fill(String),
+    // The mode already acted, exit
+    Exit,
+    // Keep onagre open and query the mode again
+    Refresh,
+    // Keep onagre open and do nothing
+    None,
+}
+
+// A mode running in the onagre process, it does not need pop-launcher
+pub trait Mode: Debug {
+    // Name of the mode, used to match `.rows[mode="..."]` style overrides
+    fn name(&self) -> &str;
+
+    // Query prefix selecting the mode, e.g. `=`
+    fn prefix(&self) -> &str;
+
+    // Name of the mode icon in the icon theme
+    fn icon(&self) -> Option<&str> {
+        None
+    }
+
+    // Entries matching the query, the mode prefix is already stripped
+    fn query(&mut self, query: &str) -> Vec<NativeEntry>;
+
+    // Entries to load in the background before `query` can be answered,
+    // handed to `on_loaded` once ready
+    fn load(&mut self, _query: &str) -> Option<Loading> {
+        None
+    }
+
+    // Called with the entries loaded for `query`, the mode is queried again afterward
+    fn on_loaded(&mut self, _query: &str, _entries: Vec<NativeEntry>) {}
+
+    // Activate the selected entry, `entry` is `None` when nothing is selected
+    fn activate(&mut self, query: &str, entry: Option<&NativeEntry>) -> Activation;
+
+    // The query replacing the current one when completing `entry`
+    fn complete(&self, entry: &NativeEntry) -> Option<String> {
+        Some(entry.name.clone())
+    }
+
+    // Whether activated entries are recorded and ranked first in the results
+    fn history(&self) -> bool {
+        false
+    }
+
+    // The value recorded in the history when `entry` is activated
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.name.clone()
+    }
+
+    // Rebuild an entry from its history key, shown when the query is empty
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        Some(NativeEntry {
+            name: key.to_string(),
+            data: key.to_string(),
+            ..Default::default()
+        })
+    }
+
+    // Called with the history keys, most used first, before querying the mode
+    fn load_history(&mut self, _keys: &[&str]) {}
+
+    // The preview pane content for `entry`
+    fn preview(&self, entry: &NativeEntry) -> Option<Preview> {
+        let description = entry.description.clone()?;
+        Some(Preview::details(
+            &entry.name,
+            vec![("Description", description)],
+        ))
+    }
+
+    // Options of the context menu opened on `entry`
+    fn context(&self, _entry: &NativeEntry) -> Vec<String> {
+        vec![]
+    }
+
+    // Activate the context option at `option` for `entry`
+    fn activate_context(&mut self, _entry: &NativeEntry, _option: usize) -> Activation {
+        Activation::None
+    }
+}
+
+// Native modes, they are matched like pop-launcher plugins
+// through the entries they add to the `PluginConfigCache`
+#[derive(Debug, Default)]
+pub struct ModeRegistry {
+    modes: Vec<RegisteredMode>,
+}
+
+// A mode and what is needed to match it before it is built
+struct RegisteredMode {
+    name: String,
+    prefix: String,
+    icon: Option<String>,
+    history: bool,
+    mode: OnceCell<Box<dyn Mode>>,
+    // Builds the mode on its first use, modes registered built do not have one
+    build: Option<Box<dyn Fn() -> Box<dyn Mode>>>,
+}
+
+impl RegisteredMode {
+    fn new(mode: &dyn Mode) -> Self {
+        RegisteredMode {
+            name: mode.name().to_string(),
+            prefix: mode.prefix().to_string(),
+            icon: mode.icon().map(str::to_string),
+            history: mode.history(),
+            mode: OnceCell::new(),
+            build: None,
+        }
+    }
+
+    fn mode(&self) -> &dyn Mode {
+        self.mode
+            .get_or_init(|| {
+                debug!("Building native mode {}", self.name);
+                let build = self
+                    .build
+                    .as_ref()
+                    .expect("Unbuilt mode should have a builder");
+                build()
+            })
+            .as_ref()
+    }
+
+    fn mode_mut(&mut self) -> &mut Box<dyn Mode> {
+        self.mode();
+        self.mode.get_mut().unwrap()
+    }
+}
+
+impl Debug for RegisteredMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredMode")
+            .field("name", &self.name)
+            .field("mode", &self.mode.get())
+            .finish()
+    }
+}
+
+impl ModeRegistry {
+    pub fn load(db: &Database) -> Self {
+        let mut registry = ModeRegistry::default();
+        registry.register_lazy(AppsMode::load);
+        registry.register(CalculatorMode::new(db.clone()));
+        registry.register_lazy(RunMode::load);
+        registry.register(WindowMode::default());
+        registry.register(ClipboardMode::new(db.clone()));
+        registry.register(EmojiMode);
+        registry.register_lazy(SshMode::load);
+        registry.register_lazy(BookmarksMode::load);
+
+        for script in scripts::list() {
+            if registry.contains(&script.name) {
+                error!(
+                    "Script mode {} shadows a built-in mode, skipping",
+                    script.name
+                );
+                continue;
+            }
+
+            registry.register(ScriptMode::new(script));
+        }
+
+        registry
+    }
+
+    pub fn register<M: Mode + 'static>(&mut self, mode: M) {
+        let registered = RegisteredMode::new(&mode);
+        let _ = registered.mode.set(Box::new(mode));
+        self.modes.push(registered);
+    }
+
+    // The mode is built on its first use, its name, prefix, icon
+    // and history setting are read from a default instance
+    pub fn register_lazy<M: Mode + Default + 'static>(&mut self, build: fn() -> M) {
+        let mut registered = RegisteredMode::new(&M::default());
+        registered.build = Some(Box::new(move || Box::new(build())));
+        self.modes.push(registered);
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.modes.iter().any(|mode| mode.name == name)
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut Box<dyn Mode>> {
+        self.modes
+            .iter_mut()
+            .find(|mode| mode.name == name)
+            .map(RegisteredMode::mode_mut)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn Mode> {
+        self.modes
+            .iter()
+            .find(|mode| mode.name == name)
+            .map(RegisteredMode::mode)
+    }
+
+    pub fn plugins(&self) -> impl Iterator<Item = Plugin> + '_ {
+        self.modes.iter().map(|mode| {
+            let icon = THEME.icon_theme.as_ref().and_then(|theme| {
+                mode.icon
+                    .as_ref()
+                    .and_then(|icon| IconPath::lookup(icon, theme, THEME.icon_size))
+            });
+
+            Plugin {
+                name: mode.name.clone(),
+                icon,
+                history: mode.history,
+                help: Some(mode.prefix.clone()),
+                regex: Regex::new(&format!("^({})+", regex::escape(&mode.prefix))).ok(),
+                native: true,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use crate::app::mode::ActiveMode;
+    use crate::app::native::{Activation, Mode, ModeRegistry, NativeEntry};
+    use speculoos::prelude::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    // Echoes the query back
+    #[derive(Debug, Default)]
+    pub(crate) struct EchoMode;
+
+    impl Mode for EchoMode {
+        fn name(&self) -> &str {
+            "echo"
+        }
+
+        fn prefix(&self) -> &str {
+            "> "
+        }
+
+        fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+            vec![NativeEntry {
+                name: query.to_string(),
+                data: query.to_string(),
+                ..Default::default()
+            }]
+        }
+
+        fn activate(&mut self, query: &str, entry: Option<&NativeEntry>) -> Activation {
+            match entry {
+                Some(entry) => Activation::Copy(entry.data.clone()),
+                None => Activation::Fill(query.to_string()),
+            }
+        }
+    }
+
+    #[test]
+    fn should_match_native_mode_prefix() {
+        let mut registry = ModeRegistry::default();
+        registry.register(EchoMode);
+
+        let plugin = registry.plugins().next().unwrap();
+        let query_data = plugin.try_match("> hello").unwrap();
+
+        assert_that!(query_data.query).is_equal_to("hello".to_string());
+        assert_that!(ActiveMode::from(query_data)).is_equal_to(ActiveMode::Native {
+            mode_name: "echo".to_string(),
+            modifier: "> ".to_string(),
+        });
+    }
+
+    #[test]
+    fn should_query_registered_mode() {
+        let mut registry = ModeRegistry::default();
+        registry.register(EchoMode);
+
+        let mode = registry.get_mut("echo").unwrap();
+        let entries = mode.query("hello");
+
+        assert_that!(mode.activate("hello", entries.first()))
+            .is_equal_to(Activation::Copy("hello".to_string()));
+        assert_that!(registry.get("unknown")).is_none();
+    }
+
+    #[test]
+    fn should_build_lazy_modes_on_first_use() {
+        static BUILT: AtomicBool = AtomicBool::new(false);
+        let mut registry = ModeRegistry::default();
+        registry.register_lazy(|| {
+            BUILT.store(true, Ordering::SeqCst);
+            EchoMode
+        });
+
+        let plugin = registry.plugins().next().unwrap();
+        assert_that!(plugin.help).is_equal_to(Some("> ".to_string()));
+        assert_that!(registry.contains("echo")).is_true();
+        assert_that!(BUILT.load(Ordering::SeqCst)).is_false();
+
+        let entries = registry.get_mut("echo").unwrap().query("hello");
+        assert_that!(entries).has_length(1);
+        assert_that!(BUILT.load(Ordering::SeqCst)).is_true();
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::BTreeSet;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use crate::app::native::{Activation, Mode, NativeEntry};
+
+pub const RUN_MODE: &str = "run";
+
+// Results listed for a partial executable name or path
+const MAX_COMPLETIONS: usize = 50;
+
+// Tried in order when `$TERMINAL` is not set
+const TERMINALS: [&str; 9] = [
+    "x-terminal-emulator",
+    "foot",
+    "alacritty",
+    "kitty",
+    "wezterm",
+    "gnome-terminal",
+    "konsole",
+    "xfce4-terminal",
+    "xterm",
+];
+
+const RUN_DESCRIPTION: &str = "Run command";
+const TERMINAL_DESCRIPTION: &str = "Run command in a terminal";
+
+// Entries data, and history keys, are the command prefixed with how it is run
+const RUN_PREFIX: &str = "run:";
+const TERMINAL_PREFIX: &str = "terminal:";
+
+// Runs shell commands, executables from `$PATH` are completed on Tab
+// and so are paths given as arguments
+#[derive(Debug, Default)]
+pub struct RunMode {
+    path: Vec<PathBuf>,
+    // Executable names, read from `path` on the first query
+    executables: Option<Vec<String>>,
+    terminal: Option<String>,
+}
+
+impl RunMode {
+    pub fn load() -> Self {
+        let path = path_dirs();
+        let terminal = default_terminal(&path);
+        RunMode::new(path, terminal)
+    }
+
+    pub fn new(path: Vec<PathBuf>, terminal: Option<String>) -> Self {
+        RunMode {
+            path,
+            executables: None,
+            terminal,
+        }
+    }
+
+    fn executables(&mut self) -> &[String] {
+        let path = &self.path;
+        self.executables.get_or_insert_with(|| {
+            let executables: BTreeSet<String> = path
+                .iter()
+                .filter_map(|dir| std::fs::read_dir(dir).ok())
+                .flat_map(|entries| entries.flatten())
+                .filter(|entry| is_executable(&entry.path()))
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect();
+
+            executables.into_iter().collect()
+        })
+    }
+
+    // The typed command, and the same command in a terminal when one is available
+    fn command_entries(&self, command: &str) -> Vec<NativeEntry> {
+        let run = NativeEntry {
+            name: command.to_string(),
+            description: Some(RUN_DESCRIPTION.to_string()),
+            icon: None,
+            glyph: None,
+            data: format!("{RUN_PREFIX}{command}"),
+        };
+
+        let terminal = self.terminal.as_ref().map(|_| NativeEntry {
+            description: Some(TERMINAL_DESCRIPTION.to_string()),
+            data: format!("{TERMINAL_PREFIX}{command}"),
+            ..run.clone()
+        });
+
+        std::iter::once(run).chain(terminal).collect()
+    }
+
+    fn complete_executable(&mut self, prefix: &str) -> Vec<NativeEntry> {
+        let mut matches: Vec<&String> = self
+            .executables()
+            .iter()
+            .filter(|name| name.starts_with(prefix))
+            .collect();
+
+        // Shortest names first, so the exact match is the first result
+        matches.sort_by_key(|name| name.len());
+        matches
+            .into_iter()
+            .take(MAX_COMPLETIONS)
+            .map(|name| NativeEntry {
+                name: name.to_string(),
+                description: None,
+                icon: None,
+                glyph: None,
+                data: format!("{RUN_PREFIX}{name}"),
+            })
+            .collect()
+    }
+}
+
+// The command held by an entry data and whether it runs in a terminal,
+// history keys recorded before the prefixes were added are plain commands
+fn parse_data(data: &str) -> (&str, bool) {
+    if let Some(command) = data.strip_prefix(TERMINAL_PREFIX) {
+        (command, true)
+    } else {
+        (data.strip_prefix(RUN_PREFIX).unwrap_or(data), false)
+    }
+}
+
+// The directories listed in `$PATH`
+pub fn path_dirs() -> Vec<PathBuf> {
+    std::env::var_os("PATH")
+        .map(|path| std::env::split_paths(&path).collect())
+        .unwrap_or_default()
+}
+
+// `$TERMINAL`, or the first common terminal emulator found in `path`
+pub fn default_terminal(path: &[PathBuf]) -> Option<String> {
+    std::env::var("TERMINAL")
+        .ok()
+        .filter(|terminal| !terminal.is_empty())
+        .or_else(|| {
+            TERMINALS
+                .iter()
+                .find(|terminal| path.iter().any(|dir| is_executable(&dir.join(terminal))))
+                .map(|terminal| terminal.to_string())
+        })
+}
+
+// Paths completing the last argument of `command`, directories end with a `/`
+fn complete_path(command: &str) -> Vec<NativeEntry> {
+    let Some((head, argument)) = command.rsplit_once(' ') else {
+        return vec![];
+    };
+
+    if !argument.contains('/') && !argument.starts_with('~') {
+        return vec![];
+    }
+
+    let (dir, prefix) = match argument.rsplit_once('/') {
+        Some((dir, prefix)) => (format!("{dir}/"), prefix),
+        None => (format!("{argument}/"), ""),
+    };
+
+    let Some(entries) = expand_home(&dir).and_then(|dir| std::fs::read_dir(dir).ok()) else {
+        return vec![];
+    };
+
+    let mut completions: Vec<(String, bool)> = entries
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            (name, entry.path().is_dir())
+        })
+        .filter(|(name, _)| name.starts_with(prefix))
+        .filter(|(name, _)| prefix.starts_with('.') || !name.starts_with('.'))
+        .collect();
+
+    completions.sort();
+    completions
+        .into_iter()
+        .take(MAX_COMPLETIONS)
+        .map(|(name, is_dir)| {
+            let suffix = if is_dir { "/" } else { "" };
+            let completed = format!("{head} {dir}{name}{suffix}");
+            NativeEntry {
+                name: completed.clone(),
+                description: Some(format!("{dir}{name}{suffix}")),
+                icon: None,
+                glyph: None,
+                data: format!("{RUN_PREFIX}{completed}"),
+            }
+        })
+        .collect()
+}
+
+fn expand_home(path: &str) -> Option<PathBuf> {
+    match path.strip_prefix('~') {
+        Some(relative) => dirs::home_dir().map(|home| home.join(relative.trim_start_matches('/'))),
+        None => Some(PathBuf::from(path)),
+    }
+}
+
+fn is_executable(path: &Path) -> bool {
+    path.metadata()
+        .map(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+impl Mode for RunMode {
+    fn name(&self) -> &str {
+        RUN_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "!"
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("utilities-terminal")
+    }
+
+    fn history(&self) -> bool {
+        true
+    }
+
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.data.clone()
+    }
+
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        let (command, _) = parse_data(key);
+        let entries = self.command_entries(command);
+        entries
+            .iter()
+            .find(|entry| entry.data == key)
+            .or(entries.first())
+            .cloned()
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        if query.is_empty() {
+            return vec![];
+        }
+
+        // The typed command first, so it is what runs on Enter
+        let mut entries = self.command_entries(query);
+        if query.contains(' ') {
+            entries.extend(complete_path(query));
+        } else {
+            entries.extend(self.complete_executable(query));
+        }
+
+        entries
+    }
+
+    fn activate(&mut self, query: &str, entry: Option<&NativeEntry>) -> Activation {
+        let (command, in_terminal) = match entry {
+            Some(entry) => parse_data(&entry.data),
+            None => (query, false),
+        };
+
+        if command.is_empty() {
+            return Activation::None;
+        }
+
+        let args = match &self.terminal {
+            Some(terminal) if in_terminal => {
+                shell_words::join([terminal.as_str(), "-e", "sh", "-c", command])
+            }
+            _ => shell_words::join(["sh", "-c", command]),
+        };
+
+        Activation::Exec(args)
+    }
+
+    fn complete(&self, entry: &NativeEntry) -> Option<String> {
+        let (command, _) = parse_data(&entry.data);
+        if command.ends_with('/') {
+            Some(command.to_string())
+        } else {
+            Some(format!("{command} "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::run::RunMode;
+    use crate::app::native::{Activation, Mode};
+    use speculoos::prelude::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn should_list_executables_from_path() {
+        let bin = tempfile::tempdir().unwrap();
+        for (name, mode) in [("firefox", 0o755), ("fish", 0o755), ("fixtures", 0o644)] {
+            let path = bin.path().join(name);
+            std::fs::write(&path, "").unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode)).unwrap();
+        }
+
+        let mut run = RunMode::new(vec![bin.path().to_path_buf()], None);
+        let names: Vec<String> = run
+            .query("fi")
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+
+        assert_that!(names).is_equal_to(vec![
+            "fi".to_string(),
+            "fish".to_string(),
+            "firefox".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn should_complete_path_arguments() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("notes")).unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+        std::fs::write(dir.join(".hidden"), "").unwrap();
+
+        let run = RunMode::new(vec![], None);
+        let command = format!("vim {}/no", dir.display());
+        let entries = super::complete_path(&command);
+        let completions: Vec<Option<String>> =
+            entries.iter().map(|entry| run.complete(entry)).collect();
+
+        assert_that!(completions).is_equal_to(vec![
+            Some(format!("vim {}/notes/", dir.display())),
+            Some(format!("vim {}/notes.txt ", dir.display())),
+        ]);
+    }
+
+    #[test]
+    fn should_run_commands_in_terminal() {
+        let mut run = RunMode::new(vec![], Some("foot".to_string()));
+        let entries = run.query("htop -d 10");
+
+        assert_that!(run.activate("htop -d 10", entries.first()))
+            .is_equal_to(Activation::Exec("sh -c 'htop -d 10'".to_string()));
+        assert_that!(run.activate("htop -d 10", entries.get(1)))
+            .is_equal_to(Activation::Exec("foot -e sh -c 'htop -d 10'".to_string()));
+    }
+
+    #[test]
+    fn should_remember_commands_run_in_terminal() {
+        let mut run = RunMode::new(vec![], Some("foot".to_string()));
+        let entries = run.query("htop");
+        let key = run.history_key(&entries[1]);
+        let entry = run.history_entry(&key).unwrap();
+
+        assert_that!(key.as_str()).is_equal_to("terminal:htop");
+        assert_that!(run.history_entry("htop").map(|entry| entry.data))
+            .is_equal_to(Some("run:htop".to_string()));
+        assert_that!(entry.description).is_equal_to(entries[1].description.clone());
+        assert_that!(run.complete(&entry)).is_equal_to(Some("htop ".to_string()));
+        assert_that!(run.activate("", Some(&entry)))
+            .is_equal_to(Activation::Exec("foot -e sh -c htop".to_string()));
+    }
+}
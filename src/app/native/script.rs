@@ -0,0 +1,400 @@
+use std::time::Duration;
+
+use serde::Deserialize;
+use tokio::process::Command;
+use tracing::error;
+
+use crate::app::native::{Activation, Loading, Mode, NativeEntry};
+use crate::config::scripts::{NamedScript, ScriptConfig, ScriptFormat};
+use crate::icons::IconPath;
+use crate::THEME;
+
+// Script commands still running after this delay are killed
+const SCRIPT_TIMEOUT: Duration = Duration::from_secs(5);
+
+// An entry of a script printing JSON
+#[derive(Debug, Deserialize)]
+struct ScriptEntry {
+    name: String,
+    description: Option<String>,
+    // An icon name in the icon theme, or an absolute path
+    icon: Option<String>,
+    // Defaults to the name
+    data: Option<String>,
+}
+
+fn icon(icon: &str) -> Option<IconPath> {
+    if icon.starts_with('/') {
+        return IconPath::from_path(icon, false);
+    }
+
+    THEME
+        .icon_theme
+        .as_ref()
+        .and_then(|theme| IconPath::lookup(icon, theme, THEME.icon_size))
+}
+
+// Entries printed by a script command
+pub fn parse_output(output: &str, format: ScriptFormat) -> Result<Vec<NativeEntry>, String> {
+    match format {
+        ScriptFormat::Lines => Ok(output
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                let (name, description) = match line.split_once('\t') {
+                    Some((name, description)) => (name, Some(description.to_string())),
+                    None => (line, None),
+                };
+
+                NativeEntry {
+                    name: name.to_string(),
+                    description,
+                    icon: None,
+                    glyph: None,
+                    data: name.to_string(),
+                }
+            })
+            .collect()),
+        ScriptFormat::Json => {
+            let entries: Vec<ScriptEntry> =
+                serde_json::from_str(output).map_err(|err| err.to_string())?;
+            Ok(entries
+                .into_iter()
+                .map(|entry| NativeEntry {
+                    data: entry.data.clone().unwrap_or_else(|| entry.name.clone()),
+                    icon: entry.icon.as_deref().and_then(icon),
+                    name: entry.name,
+                    description: entry.description,
+                    glyph: None,
+                })
+                .collect())
+        }
+    }
+}
+
+// Replace the `{data}`, `{name}` and `{query}` placeholders with shell quoted values
+pub fn expand(template: &str, entry: &NativeEntry, query: &str) -> String {
+    // A single pass, placeholders in the substituted values are left untouched
+    let placeholders = [
+        ("{data}", entry.data.as_str()),
+        ("{name}", entry.name.as_str()),
+        ("{query}", query),
+    ];
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        match placeholders
+            .iter()
+            .find(|(placeholder, _)| rest.starts_with(placeholder))
+        {
+            Some((placeholder, value)) => {
+                expanded.push_str(&shell_words::quote(value));
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                expanded.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    expanded.push_str(rest);
+    expanded
+}
+
+// Run a script command with the query, off the UI thread
+async fn run(
+    name: String,
+    command: String,
+    format: ScriptFormat,
+    query: String,
+) -> Vec<NativeEntry> {
+    let output = Command::new("sh")
+        .args(["-c", &command, &name, &query])
+        .env("ONAGRE_QUERY", &query)
+        .kill_on_drop(true)
+        .output();
+
+    let output = match tokio::time::timeout(SCRIPT_TIMEOUT, output).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            error!("Unable to run script mode {name}: {err}");
+            return vec![];
+        }
+        Err(_) => {
+            error!("Script mode {name} timed out after {SCRIPT_TIMEOUT:?}");
+            return vec![];
+        }
+    };
+
+    if !output.status.success() {
+        error!(
+            "Script mode {name} failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    parse_output(&String::from_utf8_lossy(&output.stdout), format).unwrap_or_else(|err| {
+        error!("Invalid output for script mode {name}: {err}");
+        vec![]
+    })
+}
+
+// A mode listing the output of a user command, see `ScriptConfig`
+#[derive(Debug)]
+pub struct ScriptMode {
+    name: String,
+    config: ScriptConfig,
+    // Output of the last command run, with the query it was run with
+    entries: Option<(String, Vec<NativeEntry>)>,
+    // Query of the command currently running
+    running: Option<String>,
+}
+
+impl ScriptMode {
+    pub fn new(script: NamedScript) -> Self {
+        ScriptMode {
+            name: script.name,
+            config: script.config,
+            entries: None,
+            running: None,
+        }
+    }
+
+    // Static scripts run once, whatever the query
+    fn command_query<'a>(&self, query: &'a str) -> &'a str {
+        if self.config.dynamic {
+            query
+        } else {
+            ""
+        }
+    }
+
+    // Entries printed for the query, empty until the command is done
+    fn entries(&self, query: &str) -> &[NativeEntry] {
+        let query = self.command_query(query);
+        match &self.entries {
+            Some((ran_with, entries)) if ran_with == query => entries,
+            _ => &[],
+        }
+    }
+}
+
+impl Mode for ScriptMode {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn prefix(&self) -> &str {
+        &self.config.prefix
+    }
+
+    fn icon(&self) -> Option<&str> {
+        self.config.icon.as_deref()
+    }
+
+    fn history(&self) -> bool {
+        self.config.history
+    }
+
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.data.clone()
+    }
+
+    fn load(&mut self, query: &str) -> Option<Loading> {
+        let query = self.command_query(query).to_string();
+        let done = matches!(&self.entries, Some((ran_with, _)) if *ran_with == query);
+        if done || self.running.as_ref() == Some(&query) {
+            return None;
+        }
+
+        self.running = Some(query.clone());
+        Some(Box::pin(run(
+            self.name.clone(),
+            self.config.command.clone(),
+            self.config.format,
+            query,
+        )))
+    }
+
+    fn on_loaded(&mut self, query: &str, entries: Vec<NativeEntry>) {
+        let query = self.command_query(query).to_string();
+        if self.running.as_ref() == Some(&query) {
+            self.running = None;
+        }
+
+        self.entries = Some((query, entries));
+    }
+
+    // Dynamic scripts are not run for each history entry, only the key is shown
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        let entry = if self.config.dynamic {
+            None
+        } else {
+            self.entries("")
+                .iter()
+                .find(|entry| entry.data == key)
+                .cloned()
+        };
+
+        Some(entry.unwrap_or_else(|| NativeEntry {
+            name: key.to_string(),
+            data: key.to_string(),
+            ..Default::default()
+        }))
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        if self.config.dynamic {
+            return self.entries(query).to_vec();
+        }
+
+        let query = query.to_lowercase();
+        let words: Vec<&str> = query.split_whitespace().collect();
+        self.entries("")
+            .iter()
+            .filter(|entry| {
+                let text = match &entry.description {
+                    Some(description) => format!("{} {description}", entry.name),
+                    None => entry.name.clone(),
+                };
+                let text = text.to_lowercase();
+                words.iter().all(|word| text.contains(word))
+            })
+            .cloned()
+            .collect()
+    }
+
+    // Without a selected entry the query itself is activated
+    fn activate(&mut self, query: &str, entry: Option<&NativeEntry>) -> Activation {
+        let entry = match entry {
+            Some(entry) => entry.clone(),
+            None if !query.is_empty() => NativeEntry {
+                name: query.to_string(),
+                data: query.to_string(),
+                ..Default::default()
+            },
+            None => return Activation::None,
+        };
+
+        match &self.config.exec {
+            Some(template) => {
+                let command = expand(template, &entry, query);
+                Activation::Exec(shell_words::join(["sh", "-c", &command]))
+            }
+            None => Activation::Copy(entry.data),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::script::{expand, parse_output, ScriptMode};
+    use crate::app::native::{Activation, Mode, NativeEntry};
+    use crate::config::scripts::{NamedScript, ScriptConfig, ScriptFormat};
+    use speculoos::prelude::*;
+
+    fn script(command: &str, format: ScriptFormat, dynamic: bool) -> ScriptMode {
+        ScriptMode::new(NamedScript {
+            name: "test".to_string(),
+            config: ScriptConfig {
+                prefix: "test ".to_string(),
+                command: command.to_string(),
+                format,
+                exec: Some("notify-send {data} {query}".to_string()),
+                history: false,
+                icon: None,
+                dynamic,
+            },
+        })
+    }
+
+    // Run the script command as onagre does before querying the mode
+    async fn load(mode: &mut ScriptMode, query: &str) {
+        if let Some(loading) = mode.load(query) {
+            let entries = loading.await;
+            mode.on_loaded(query, entries);
+        }
+    }
+
+    #[test]
+    fn should_parse_lines_and_json() {
+        let lines = parse_output("Lock\tScreen\n\nReboot\n", ScriptFormat::Lines).unwrap();
+        let json = parse_output(
+            r#"[{ "name": "Home", "description": "~", "data": "/home/user" }]"#,
+            ScriptFormat::Json,
+        )
+        .unwrap();
+
+        assert_that!(lines).has_length(2);
+        assert_that!(lines[0].description).is_equal_to(Some("Screen".to_string()));
+        assert_that!(lines[1].data).is_equal_to("Reboot".to_string());
+        assert_that!(json[0].data).is_equal_to("/home/user".to_string());
+        assert_that!(parse_output("not json", ScriptFormat::Json)).is_err();
+    }
+
+    #[tokio::test]
+    async fn should_filter_static_output() {
+        let mut mode = script(
+            "printf 'Lock\\nSuspend\\nReboot\\n'",
+            ScriptFormat::Lines,
+            false,
+        );
+        assert_that!(mode.query("re")).is_empty();
+
+        load(&mut mode, "re").await;
+        // Static scripts only run once
+        assert_that!(mode.load("reb").is_none()).is_true();
+        let entries = mode.query("re");
+
+        let names: Vec<&str> = entries.iter().map(|entry| entry.name.as_str()).collect();
+
+        assert_that!(names).is_equal_to(vec!["Reboot"]);
+        assert_that!(mode.activate("re", entries.first())).is_equal_to(Activation::Exec(
+            "sh -c 'notify-send Reboot re'".to_string(),
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_run_dynamic_scripts_with_the_query() {
+        let mut mode = script("echo \"result for $1\"", ScriptFormat::Lines, true);
+        load(&mut mode, "two words").await;
+
+        let names: Vec<String> = mode
+            .query("two words")
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect();
+
+        assert_that!(names).is_equal_to(vec!["result for two words".to_string()]);
+        assert_that!(mode.query("other")).is_empty();
+    }
+
+    #[tokio::test]
+    async fn should_kill_scripts_running_too_long() {
+        let mut mode = script("sleep 10; echo late", ScriptFormat::Lines, true);
+
+        tokio::time::pause();
+        load(&mut mode, "").await;
+
+        assert_that!(mode.query("")).is_empty();
+    }
+
+    #[test]
+    fn should_not_expand_placeholders_in_values() {
+        let entry = NativeEntry {
+            name: "name".to_string(),
+            data: "{query}; rm -rf ~".to_string(),
+            ..Default::default()
+        };
+
+        let command = expand("echo {data} {query} {unknown}", &entry, "$(reboot)");
+
+        assert_that!(command)
+            .is_equal_to("echo '{query}; rm -rf ~' '$(reboot)' {unknown}".to_string());
+    }
+}
@@ -0,0 +1,398 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, error};
+
+use crate::app::native::run::{default_terminal, path_dirs};
+use crate::app::native::{Activation, Mode, NativeEntry};
+
+pub const SSH_MODE: &str = "ssh";
+
+// Nested `Include` directives followed, like ssh does
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+// A host from the ssh config or the known hosts
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SshHost {
+    // The `Host` alias, or the known host name
+    pub alias: String,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    // Only set for known hosts, ssh reads it from the config otherwise
+    pub port: Option<u16>,
+}
+
+impl SshHost {
+    fn new(alias: &str) -> Self {
+        SshHost {
+            alias: alias.to_string(),
+            ..Default::default()
+        }
+    }
+
+    // The row name, known hosts on a non default port are shown as `host:port`
+    fn name(&self) -> String {
+        match self.port {
+            Some(port) => format!("{}:{port}", self.alias),
+            None => self.alias.clone(),
+        }
+    }
+
+    // `user@hostname`, when they differ from the alias
+    fn description(&self) -> Option<String> {
+        let hostname = self
+            .hostname
+            .as_deref()
+            .filter(|hostname| *hostname != self.alias);
+
+        match (&self.user, hostname) {
+            (Some(user), Some(hostname)) => Some(format!("{user}@{hostname}")),
+            (Some(user), None) => Some(format!("{user}@{}", self.alias)),
+            (None, Some(hostname)) => Some(hostname.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    fn command(&self) -> Vec<String> {
+        let mut command = vec!["ssh".to_string()];
+        if let Some(port) = self.port {
+            command.extend(["-p".to_string(), port.to_string()]);
+        }
+
+        command.push(self.alias.clone());
+        command
+    }
+}
+
+// Keyword and arguments of a config line, they are separated by spaces or an `=`
+fn config_line(line: &str) -> Option<(String, &str)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (keyword, arguments) = line
+        .split_once(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or((line, ""));
+    let arguments = arguments.trim_start().trim_start_matches('=').trim_start();
+
+    Some((keyword.to_lowercase(), arguments))
+}
+
+fn is_pattern(host: &str) -> bool {
+    host.contains(['*', '?', '!'])
+}
+
+// Relative includes are resolved from `~/.ssh`
+fn include_paths(pattern: &str, ssh_dir: &Path) -> Vec<PathBuf> {
+    let pattern = match pattern.strip_prefix("~/") {
+        Some(relative) => dirs::home_dir().map(|home| home.join(relative)),
+        None if Path::new(pattern).is_absolute() => Some(PathBuf::from(pattern)),
+        None => Some(ssh_dir.join(pattern)),
+    };
+
+    let Some(pattern) = pattern else {
+        return vec![];
+    };
+
+    let mut paths: Vec<PathBuf> = glob::glob(&pattern.to_string_lossy())
+        .map(|paths| paths.flatten().collect())
+        .unwrap_or_default();
+    paths.sort();
+    paths
+}
+
+// Hosts declared in an ssh config file and its includes,
+// `Host` patterns and `Match` blocks are skipped
+pub fn parse_config(path: &Path, ssh_dir: &Path) -> Vec<SshHost> {
+    let mut hosts = vec![];
+    read_config(path, ssh_dir, 0, &mut hosts);
+    hosts
+}
+
+fn read_config(path: &Path, ssh_dir: &Path, depth: usize, hosts: &mut Vec<SshHost>) {
+    if depth > MAX_INCLUDE_DEPTH {
+        error!("Too many nested includes in {path:?}");
+        return;
+    }
+
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => {
+            debug!("Unable to read ssh config {path:?}: {err}");
+            return;
+        }
+    };
+
+    // Index of the hosts of the current `Host` block
+    let mut block = hosts.len()..hosts.len();
+    for (keyword, arguments) in content.lines().filter_map(config_line) {
+        match keyword.as_str() {
+            "host" => {
+                let start = hosts.len();
+                hosts.extend(
+                    arguments
+                        .split_whitespace()
+                        .filter(|host| !is_pattern(host))
+                        .map(SshHost::new),
+                );
+                block = start..hosts.len();
+            }
+            "match" => block = hosts.len()..hosts.len(),
+            "include" => {
+                for include in arguments.split_whitespace() {
+                    for path in include_paths(include, ssh_dir) {
+                        read_config(&path, ssh_dir, depth + 1, hosts);
+                    }
+                }
+            }
+            // The first value obtained is used by ssh
+            "hostname" => {
+                for host in &mut hosts[block.clone()] {
+                    host.hostname.get_or_insert_with(|| arguments.to_string());
+                }
+            }
+            "user" => {
+                for host in &mut hosts[block.clone()] {
+                    host.user.get_or_insert_with(|| arguments.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+// Hosts of a `known_hosts` file, hashed host names can't be listed
+pub fn parse_known_hosts(content: &str) -> Vec<SshHost> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        // `@cert-authority` and `@revoked` lines hold patterns rather than hosts
+        .filter(|line| !line.starts_with('@'))
+        .filter_map(|line| line.split_whitespace().next())
+        .flat_map(|hosts| hosts.split(','))
+        .filter(|host| !host.starts_with('|') && !is_pattern(host))
+        .map(|host| {
+            match host
+                .strip_prefix('[')
+                .and_then(|host| host.split_once("]:"))
+            {
+                Some((host, port)) => SshHost {
+                    port: port.parse().ok(),
+                    ..SshHost::new(host)
+                },
+                None => SshHost::new(host),
+            }
+        })
+        .collect()
+}
+
+// Hosts from `~/.ssh/config` followed by the ones from `~/.ssh/known_hosts`,
+// sessions are opened in a terminal
+#[derive(Debug, Default)]
+pub struct SshMode {
+    ssh_dir: PathBuf,
+    // Read on the first query
+    hosts: Option<Vec<SshHost>>,
+    terminal: Option<String>,
+}
+
+impl SshMode {
+    pub fn load() -> Self {
+        let ssh_dir = dirs::home_dir().unwrap_or_default().join(".ssh");
+        SshMode::new(ssh_dir, default_terminal(&path_dirs()))
+    }
+
+    pub fn new(ssh_dir: PathBuf, terminal: Option<String>) -> Self {
+        SshMode {
+            ssh_dir,
+            hosts: None,
+            terminal,
+        }
+    }
+
+    fn hosts(&mut self) -> &[SshHost] {
+        let ssh_dir = &self.ssh_dir;
+        self.hosts.get_or_insert_with(|| {
+            let mut hosts = parse_config(&ssh_dir.join("config"), ssh_dir);
+            let known_hosts = std::fs::read_to_string(ssh_dir.join("known_hosts"))
+                .map(|content| parse_known_hosts(&content))
+                .unwrap_or_default();
+            hosts.extend(known_hosts);
+
+            // A host declared in several blocks, or already known from the config
+            let mut names = HashSet::new();
+            hosts.retain(|host| names.insert(host.name()));
+            hosts
+        })
+    }
+
+    fn host(&mut self, name: &str) -> Option<SshHost> {
+        self.hosts()
+            .iter()
+            .find(|host| host.name() == name)
+            .cloned()
+    }
+}
+
+fn host_entry(host: &SshHost) -> NativeEntry {
+    NativeEntry {
+        name: host.name(),
+        description: host.description(),
+        icon: None,
+        glyph: None,
+        data: host.name(),
+    }
+}
+
+impl Mode for SshMode {
+    fn name(&self) -> &str {
+        SSH_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "ssh "
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("network-server")
+    }
+
+    fn history(&self) -> bool {
+        true
+    }
+
+    fn history_key(&self, entry: &NativeEntry) -> String {
+        entry.data.clone()
+    }
+
+    fn history_entry(&mut self, key: &str) -> Option<NativeEntry> {
+        self.host(key).map(|host| host_entry(&host))
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let query = query.to_lowercase();
+        self.hosts()
+            .iter()
+            .filter(|host| {
+                host.name().to_lowercase().contains(&query)
+                    || host
+                        .description()
+                        .is_some_and(|description| description.to_lowercase().contains(&query))
+            })
+            .map(host_entry)
+            .collect()
+    }
+
+    fn activate(&mut self, query: &str, entry: Option<&NativeEntry>) -> Activation {
+        let host = match entry {
+            Some(entry) => self.host(&entry.data),
+            None if !query.is_empty() => Some(SshHost::new(query)),
+            None => None,
+        };
+
+        let Some(host) = host else {
+            return Activation::None;
+        };
+
+        let Some(terminal) = &self.terminal else {
+            error!("No terminal found to open an ssh session, set $TERMINAL");
+            return Activation::None;
+        };
+
+        let mut command = vec![terminal.clone(), "-e".to_string()];
+        command.extend(host.command());
+        Activation::Exec(shell_words::join(command))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::ssh::{parse_config, parse_known_hosts, SshHost, SshMode};
+    use crate::app::native::{Activation, Mode};
+    use speculoos::prelude::*;
+    use tempfile::TempDir;
+
+    fn ssh_dir() -> TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("config.d")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn should_parse_config_hosts_and_includes() {
+        let temp = ssh_dir();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("config"),
+            "Include config.d/*\n\
+             Host web web-alias *.internal\n  HostName=10.0.0.2\n  User deploy\n\
+             Match host db\n  User ignored\n\
+             Host *\n  User nobody\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("config.d/work"),
+            "Host db\n    HostName db.example.com\n",
+        )
+        .unwrap();
+
+        let hosts = parse_config(&dir.join("config"), dir);
+        let web = SshHost {
+            alias: "web".to_string(),
+            hostname: Some("10.0.0.2".to_string()),
+            user: Some("deploy".to_string()),
+            port: None,
+        };
+
+        assert_that!(hosts).is_equal_to(vec![
+            SshHost {
+                alias: "db".to_string(),
+                hostname: Some("db.example.com".to_string()),
+                ..Default::default()
+            },
+            web.clone(),
+            SshHost {
+                alias: "web-alias".to_string(),
+                ..web.clone()
+            },
+        ]);
+        assert_that!(web.description()).is_equal_to(Some("deploy@10.0.0.2".to_string()));
+    }
+
+    #[test]
+    fn should_parse_known_hosts() {
+        let hosts = parse_known_hosts(
+            "github.com,140.82.121.4 ssh-ed25519 AAAA\n\
+             [git.example.com]:2222 ssh-rsa AAAA\n\
+             |1|hashed= ssh-rsa AAAA\n\
+             @revoked *.example.com ssh-rsa AAAA\n",
+        );
+
+        let names: Vec<String> = hosts.iter().map(SshHost::name).collect();
+
+        assert_that!(names).is_equal_to(vec![
+            "github.com".to_string(),
+            "140.82.121.4".to_string(),
+            "git.example.com:2222".to_string(),
+        ]);
+    }
+
+    #[test]
+    fn should_open_sessions_in_terminal() {
+        let temp = ssh_dir();
+        let dir = temp.path();
+        std::fs::write(dir.join("config"), "Host web\n  HostName 10.0.0.2\n").unwrap();
+        std::fs::write(dir.join("known_hosts"), "[web]:2222 ssh-rsa AAAA\n").unwrap();
+
+        let mut ssh = SshMode::new(dir.to_path_buf(), Some("foot".to_string()));
+        let entries = ssh.query("web");
+
+        assert_that!(entries).has_length(2);
+        assert_that!(ssh.activate("web", entries.first()))
+            .is_equal_to(Activation::Exec("foot -e ssh web".to_string()));
+        assert_that!(ssh.activate("web", entries.get(1)))
+            .is_equal_to(Activation::Exec("foot -e ssh -p 2222 web".to_string()));
+    }
+}
@@ -0,0 +1,266 @@
+use std::collections::HashMap;
+
+use tracing::{error, warn};
+
+use crate::app::native::apps::application_dirs;
+use crate::app::native::{Activation, Mode, NativeEntry};
+use crate::freedesktop::desktop::DesktopEntry;
+use crate::freedesktop::toplevel::{self, Toplevel, ToplevelProvider};
+use crate::icons::IconPath;
+use crate::THEME;
+
+pub const WINDOWS_MODE: &str = "window";
+
+// Application ID of the onagre window, never listed
+const ONAGRE_APP_ID: &str = "onagre";
+
+const CONTEXT_OPTIONS: [&str; 2] = ["Focus", "Close"];
+
+// Lists the open windows, focuses the selected one or closes it from the context menu
+#[derive(Debug, Default)]
+pub struct WindowMode {
+    // Connected on the first query
+    provider: Option<Box<dyn ToplevelProvider>>,
+    connected: bool,
+    icons: HashMap<String, Option<IconPath>>,
+}
+
+impl WindowMode {
+    pub fn new(provider: Box<dyn ToplevelProvider>) -> Self {
+        WindowMode {
+            provider: Some(provider),
+            connected: true,
+            icons: HashMap::new(),
+        }
+    }
+
+    fn provider(&mut self) -> Option<&mut Box<dyn ToplevelProvider>> {
+        if !self.connected {
+            self.connected = true;
+            self.provider = toplevel::connect();
+        }
+
+        self.provider.as_mut()
+    }
+
+    // The icon of the desktop entry named after the app ID, or the icon named like the app ID
+    fn icon(icons: &mut HashMap<String, Option<IconPath>>, app_id: &str) -> Option<IconPath> {
+        let theme = THEME.icon_theme.as_ref()?;
+        icons
+            .entry(app_id.to_string())
+            .or_insert_with(|| {
+                let names = [app_id.to_string(), app_id.to_lowercase()];
+                let desktop_icon = application_dirs()
+                    .iter()
+                    .flat_map(|dir| {
+                        names
+                            .iter()
+                            .map(move |name| dir.join(format!("{name}.desktop")))
+                    })
+                    .find_map(|path| {
+                        DesktopEntry::from_path(path)?
+                            .icon
+                            .map(|icon| icon.to_string())
+                    });
+
+                desktop_icon
+                    .into_iter()
+                    .chain(names)
+                    .find_map(|icon| IconPath::lookup(&icon, theme, THEME.icon_size))
+            })
+            .clone()
+    }
+
+    fn act(
+        &mut self,
+        entry: &NativeEntry,
+        action: fn(&mut dyn ToplevelProvider, u64) -> Result<(), toplevel::ToplevelError>,
+    ) -> bool {
+        let (Some(provider), Ok(id)) = (self.provider(), entry.data.parse::<u64>()) else {
+            return false;
+        };
+
+        match action(provider.as_mut(), id) {
+            Ok(()) => true,
+            Err(err) => {
+                error!("Unable to manage window '{}': {err}", entry.name);
+                false
+            }
+        }
+    }
+}
+
+fn matches(toplevel: &Toplevel, query: &str) -> bool {
+    toplevel.title.to_lowercase().contains(query) || toplevel.app_id.to_lowercase().contains(query)
+}
+
+impl Mode for WindowMode {
+    fn name(&self) -> &str {
+        WINDOWS_MODE
+    }
+
+    fn prefix(&self) -> &str {
+        "window "
+    }
+
+    fn icon(&self) -> Option<&str> {
+        Some("preferences-system-windows")
+    }
+
+    fn query(&mut self, query: &str) -> Vec<NativeEntry> {
+        let Some(provider) = self.provider() else {
+            return vec![];
+        };
+
+        let mut toplevels = match provider.toplevels() {
+            Ok(toplevels) => toplevels,
+            Err(err) => {
+                warn!("Unable to list windows: {err}");
+                return vec![];
+            }
+        };
+
+        // The focused window is the least likely to be switched to
+        toplevels.sort_by_key(|toplevel| toplevel.active);
+
+        let query = query.to_lowercase();
+        toplevels
+            .into_iter()
+            .filter(|toplevel| toplevel.app_id != ONAGRE_APP_ID && matches(toplevel, &query))
+            .map(|toplevel| NativeEntry {
+                icon: Self::icon(&mut self.icons, &toplevel.app_id),
+                name: toplevel.title,
+                description: Some(toplevel.app_id).filter(|app_id| !app_id.is_empty()),
+                data: toplevel.id.to_string(),
+                glyph: None,
+            })
+            .collect()
+    }
+
+    fn activate(&mut self, _query: &str, entry: Option<&NativeEntry>) -> Activation {
+        match entry {
+            Some(entry) if self.act(entry, |provider, id| provider.focus(id)) => Activation::Exit,
+            _ => Activation::None,
+        }
+    }
+
+    fn complete(&self, _entry: &NativeEntry) -> Option<String> {
+        None
+    }
+
+    fn context(&self, _entry: &NativeEntry) -> Vec<String> {
+        CONTEXT_OPTIONS
+            .iter()
+            .map(|option| option.to_string())
+            .collect()
+    }
+
+    fn activate_context(&mut self, entry: &NativeEntry, option: usize) -> Activation {
+        match option {
+            0 => self.activate("", Some(entry)),
+            _ if self.act(entry, |provider, id| provider.close(id)) => Activation::Refresh,
+            _ => Activation::None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::native::windows::WindowMode;
+    use crate::app::native::{Activation, Mode};
+    use crate::freedesktop::toplevel::{Toplevel, ToplevelError, ToplevelProvider};
+    use speculoos::prelude::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    // Windows kept in memory, shared with the test to check the requests
+    #[derive(Debug, Default, Clone)]
+    struct MockToplevels {
+        windows: Rc<RefCell<Vec<Toplevel>>>,
+        focused: Rc<RefCell<Option<u64>>>,
+    }
+
+    impl MockToplevels {
+        fn with(windows: &[(u64, &str, &str, bool)]) -> Self {
+            let windows = windows
+                .iter()
+                .map(|(id, title, app_id, active)| Toplevel {
+                    id: *id,
+                    title: title.to_string(),
+                    app_id: app_id.to_string(),
+                    active: *active,
+                })
+                .collect();
+
+            MockToplevels {
+                windows: Rc::new(RefCell::new(windows)),
+                focused: Default::default(),
+            }
+        }
+    }
+
+    impl ToplevelProvider for MockToplevels {
+        fn toplevels(&mut self) -> Result<Vec<Toplevel>, ToplevelError> {
+            Ok(self.windows.borrow().clone())
+        }
+
+        fn focus(&mut self, id: u64) -> Result<(), ToplevelError> {
+            *self.focused.borrow_mut() = Some(id);
+            Ok(())
+        }
+
+        fn close(&mut self, id: u64) -> Result<(), ToplevelError> {
+            let mut windows = self.windows.borrow_mut();
+            let count = windows.len();
+            windows.retain(|window| window.id != id);
+            if windows.len() == count {
+                Err(ToplevelError::UnknownWindow(id))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    fn titles(mode: &mut WindowMode, query: &str) -> Vec<String> {
+        mode.query(query)
+            .into_iter()
+            .map(|entry| entry.name)
+            .collect()
+    }
+
+    #[test]
+    fn should_list_windows_focused_last() {
+        let provider = MockToplevels::with(&[
+            (1, "Terminal", "foot", true),
+            (2, "Inbox - Thunderbird", "thunderbird", false),
+            (3, "Onagre", "onagre", false),
+            (4, "notes.md - Helix", "foot", false),
+        ]);
+        let mut mode = WindowMode::new(Box::new(provider));
+
+        assert_that!(titles(&mut mode, "")).is_equal_to(vec![
+            "Inbox - Thunderbird".to_string(),
+            "notes.md - Helix".to_string(),
+            "Terminal".to_string(),
+        ]);
+        assert_that!(titles(&mut mode, "FOOT"))
+            .is_equal_to(vec!["notes.md - Helix".to_string(), "Terminal".to_string()]);
+    }
+
+    #[test]
+    fn should_focus_and_close_windows() {
+        let provider = MockToplevels::with(&[
+            (1, "Terminal", "foot", false),
+            (2, "Inbox - Thunderbird", "thunderbird", false),
+        ]);
+        let mut mode = WindowMode::new(Box::new(provider.clone()));
+        let entries = mode.query("");
+
+        assert_that!(mode.activate("", entries.get(1))).is_equal_to(Activation::Exit);
+        assert_that!(*provider.focused.borrow()).is_equal_to(Some(2));
+
+        assert_that!(mode.activate_context(&entries[0], 1)).is_equal_to(Activation::Refresh);
+        assert_that!(titles(&mut mode, "")).is_equal_to(vec!["Inbox - Thunderbird".to_string()]);
+        assert_that!(mode.activate_context(&entries[0], 1)).is_equal_to(Activation::None);
+    }
+}
@@ -9,6 +9,8 @@ pub struct Plugin {
     pub history: bool,
     pub help: Option<String>,
     pub regex: Option<Regex>,
+    // Native modes run in onagre rather than in pop-launcher
+    pub native: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +20,7 @@ pub struct QueryData {
     pub modifier: String,
     pub query: String,
     pub history: bool,
+    pub native: bool,
 }
 
 impl QueryData {
@@ -28,6 +31,7 @@ impl QueryData {
             modifier: modifier.as_ref().to_string(),
             history: true,
             query,
+            native: false,
         }
     }
 }
@@ -40,6 +44,7 @@ impl Plugin {
             modifier: self.name.clone(),
             query,
             history: self.history,
+            native: self.native,
         }
     }
 
@@ -50,6 +55,7 @@ impl Plugin {
             modifier: modifier.as_ref().to_string(),
             query,
             history: self.history,
+            native: self.native,
         }
     }
 }
@@ -119,6 +125,7 @@ mod test {
             history: false,
             help: Some("find ".to_string()),
             regex: Some(Regex::new("^(find )+").unwrap()),
+            native: false,
         };
 
         let match_ = plugin.try_match("find some text");
@@ -131,6 +138,7 @@ mod test {
                 modifier: "find".to_string(),
                 query: " some text".to_string(),
                 history: false,
+                native: false,
             })
         );
     }
@@ -143,6 +151,7 @@ mod test {
             history: false,
             help: Some("find ".to_string()),
             regex: Some(Regex::new("^(find )+").unwrap()),
+            native: false,
         };
 
         let match_ = plugin.try_match("fin");
@@ -158,6 +167,7 @@ mod test {
             history: false,
             help: Some("find ".to_string()),
             regex: Some(Regex::new("^(find )+").unwrap()),
+            native: false,
         };
 
         let match_ = plugin.try_match("find ");
@@ -170,6 +180,7 @@ mod test {
                 modifier: "find".to_string(),
                 query: " ".to_string(),
                 history: false,
+                native: false,
             })
         );
     }
@@ -0,0 +1,272 @@
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use onagre_launcher_toolkit::plugins::web::Definition;
+
+use crate::freedesktop::desktop::{split_list, DesktopEntry};
+
+// Lines of a text file shown in the preview pane
+const TEXT_LINES: usize = 20;
+// Bytes read from a text file, enough to fill the preview without reading huge files
+const TEXT_BYTES: u64 = 8 * 1024;
+// Entries listed when previewing a directory
+const DIRECTORY_ENTRIES: usize = 20;
+
+const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "bmp", "webp", "ico"];
+
+// A richer view of the selected entry, displayed in the preview pane
+#[derive(Debug, Clone, PartialEq)]
+pub struct Preview {
+    pub title: String,
+    pub content: PreviewContent,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewContent {
+    // The head of a text file
+    Text(String),
+    Image(PathBuf),
+    Svg(PathBuf),
+    // Label and value pairs
+    Details(Vec<(&'static str, String)>),
+}
+
+// What the preview pane shows, known without reading any file.
+// The preview is only loaded again when the source changes
+#[derive(Debug, Clone, PartialEq)]
+pub enum PreviewSource {
+    // Nothing to read, e.g. a web query or a native mode entry
+    Ready(Option<Preview>),
+    DesktopEntry(PathBuf),
+    // A file, with the title to display in place of its name
+    File(PathBuf, Option<String>),
+    // A pop-launcher result, possibly a file
+    PopResult { name: String, description: String },
+}
+
+impl PreviewSource {
+    pub fn load(self) -> Option<Preview> {
+        match self {
+            PreviewSource::Ready(preview) => preview,
+            PreviewSource::DesktopEntry(path) => Preview::desktop_entry(path),
+            PreviewSource::File(path, title) => Preview::file(path).map(|preview| Preview {
+                title: title.unwrap_or(preview.title),
+                ..preview
+            }),
+            PreviewSource::PopResult { name, description } => result_path(&name, &description)
+                .and_then(Preview::file)
+                .or_else(|| {
+                    (!description.is_empty())
+                        .then(|| Preview::details(&name, vec![("Description", description)]))
+                }),
+        }
+    }
+}
+
+impl Preview {
+    pub fn details(title: &str, details: Vec<(&'static str, String)>) -> Self {
+        Preview {
+            title: title.to_string(),
+            content: PreviewContent::Details(details),
+        }
+    }
+
+    // Exec line, location and categories of a desktop entry
+    pub fn desktop_entry<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let path = path.as_ref();
+        let entry = DesktopEntry::from_path(path)?;
+        let mut details = vec![
+            ("Exec", entry.exec.to_string()),
+            ("Path", path.to_string_lossy().to_string()),
+        ];
+
+        if let Some(categories) = &entry.categories {
+            let categories = split_list(categories).collect::<Vec<&str>>().join(", ");
+            details.push(("Categories", categories));
+        }
+
+        if let Some(comment) = &entry.comment {
+            details.push(("Comment", comment.to_string()));
+        }
+
+        Some(Preview::details(&entry.name, details))
+    }
+
+    // Thumbnail of an image, head of a text file or content of a directory
+    pub fn file<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let path = path.as_ref();
+        let title = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.to_string_lossy().to_string());
+
+        let content = if path.is_dir() {
+            PreviewContent::Text(list_directory(path)?)
+        } else {
+            let extension = path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_lowercase());
+
+            match extension.as_deref() {
+                Some("svg") => PreviewContent::Svg(path.to_path_buf()),
+                Some(extension) if IMAGE_EXTENSIONS.contains(&extension) => {
+                    PreviewContent::Image(path.to_path_buf())
+                }
+                _ => PreviewContent::Text(text_head(path)?),
+            }
+        };
+
+        Some(Preview { title, content })
+    }
+
+    // The url a web query will open
+    pub fn web(query: &str, definition: &Definition) -> Self {
+        Preview::details(
+            &definition.name,
+            vec![
+                ("Query", query.to_string()),
+                ("Url", web_url(definition, query)),
+            ],
+        )
+    }
+}
+
+// Resolve a pop-launcher result to an existing file, file plugins either
+// describe entries with their full path or with their parent directory
+pub fn result_path(name: &str, description: &str) -> Option<PathBuf> {
+    let expand = |path: &str| match path.strip_prefix("~/") {
+        Some(relative) => dirs::home_dir().map(|home| home.join(relative)),
+        None => Some(PathBuf::from(path)),
+    };
+
+    let description = expand(description.trim())?;
+    if !description.is_absolute() {
+        return None;
+    }
+
+    let candidates = [description.join(name), description];
+    IntoIterator::into_iter(candidates).find(|path| path.exists())
+}
+
+// First lines of a text file, binary files have no preview
+fn text_head(path: &Path) -> Option<String> {
+    let mut buffer = vec![];
+    std::fs::File::open(path)
+        .ok()?
+        .take(TEXT_BYTES)
+        .read_to_end(&mut buffer)
+        .ok()?;
+
+    if buffer.contains(&0) {
+        return None;
+    }
+
+    let content = String::from_utf8_lossy(&buffer);
+    Some(
+        content
+            .lines()
+            .take(TEXT_LINES)
+            .collect::<Vec<&str>>()
+            .join("\n"),
+    )
+}
+
+fn list_directory(path: &Path) -> Option<String> {
+    let mut entries: Vec<String> = std::fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.path().is_dir() {
+                format!("{name}/")
+            } else {
+                name
+            }
+        })
+        .collect();
+
+    entries.sort();
+    entries.truncate(DIRECTORY_ENTRIES);
+    Some(entries.join("\n"))
+}
+
+// Same as the pop-launcher web plugin, queries without a scheme are https urls
+fn web_url(definition: &Definition, query: &str) -> String {
+    let has_scheme = definition
+        .query
+        .split_once(':')
+        .map(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+        .unwrap_or(false);
+
+    let prefix = if has_scheme { "" } else { "https://" };
+    format!("{prefix}{}{}", definition.query, url_encode(query))
+}
+
+fn url_encode(query: &str) -> String {
+    query
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::preview::{result_path, text_head, web_url, PreviewContent, PreviewSource};
+    use onagre_launcher_toolkit::plugins::web::Definition;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_preview_text_head() {
+        let temp = tempfile::tempdir().unwrap();
+        let file = temp.path().join("notes.txt");
+        let content: Vec<String> = (0..50).map(|line| format!("line {line}")).collect();
+        std::fs::write(&file, content.join("\n")).unwrap();
+
+        let head = text_head(&file).unwrap();
+
+        assert_that!(head.lines().count()).is_equal_to(20);
+        assert_that!(head.lines().last()).is_equal_to(Some("line 19"));
+    }
+
+    #[test]
+    fn should_resolve_file_results() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("picture.png"), "").unwrap();
+        let parent = dir.to_string_lossy();
+
+        let path = result_path("picture.png", &parent).unwrap();
+        let preview = PreviewSource::PopResult {
+            name: "picture.png".to_string(),
+            description: parent.to_string(),
+        }
+        .load()
+        .unwrap();
+
+        assert_that!(path).is_equal_to(dir.join("picture.png"));
+        assert_that!(preview.content).is_equal_to(PreviewContent::Image(dir.join("picture.png")));
+        assert_that!(result_path("Firefox", "Web browser")).is_none();
+    }
+
+    #[test]
+    fn should_build_web_url() {
+        let definition = Definition {
+            name: "DuckDuckGo".to_string(),
+            query: "duckduckgo.com/?q=".to_string(),
+            icon: String::new(),
+        };
+
+        assert_that!(web_url(&definition, "rust iced"))
+            .is_equal_to("https://duckduckgo.com/?q=rust%20iced".to_string());
+    }
+}
@@ -1,13 +1,18 @@
 use crate::app::cache::Cache;
-use crate::app::mode::ActiveMode;
-use crate::app::plugin_matchers::{match_web_plugins, Plugin};
-use onagre_launcher_toolkit::launcher::SearchResult;
+use crate::app::mode::{ActiveMode, THEMES_MODE};
+use crate::app::native::apps::APPS_MODE;
+use crate::app::native::{ModeRegistry, NativeEntry};
+use crate::app::plugin_matchers::{match_web_plugins, Plugin, QueryData};
+use crate::app::preview::{Preview, PreviewSource};
+use onagre_launcher_toolkit::launcher::{ContextOption, SearchResult};
 use tracing::debug;
 
 use crate::app::{Message, INPUT_ID};
+use crate::config::themes::{self, NamedTheme};
 use crate::icons::IconPath;
 use crate::THEME;
 use iced::widget::{scrollable, text_input};
+use regex::Regex;
 use std::collections::HashMap;
 
 #[derive(Debug)]
@@ -19,6 +24,37 @@ pub struct State<'a> {
     pub scroll: scrollable::State,
     pub exec_on_next_search: bool,
     pub plugin_matchers: PluginConfigCache,
+    pub themes: Vec<NamedTheme>,
+    // Preview of the selected entry, only computed when the theme has a preview pane
+    pub preview: Option<Preview>,
+    // What the preview was last loaded from
+    pub preview_source: Option<PreviewSource>,
+    // Context options of a pop-launcher result, displayed in place of the results
+    pub context_menu: Option<ContextMenu>,
+    pub backend: BackendStatus,
+    pub native_modes: ModeRegistry,
+    // Results of the active native mode
+    pub native_search: Vec<NativeEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendStatus {
+    // pop-launcher is not connected yet
+    #[default]
+    Starting,
+    // A search was sent, waiting for its results
+    Searching,
+    Ready,
+    // pop-launcher could not be started, only native modes work
+    Unavailable,
+}
+
+#[derive(Debug)]
+pub struct ContextMenu {
+    // The pop-launcher result the options apply to
+    pub result: u32,
+    pub options: Vec<ContextOption>,
+    pub selected: usize,
 }
 
 #[derive(Debug)]
@@ -27,7 +63,7 @@ pub struct PluginConfigCache {
 }
 
 impl PluginConfigCache {
-    pub fn load() -> Self {
+    pub fn load(native_modes: &ModeRegistry) -> Self {
         let mut cache = HashMap::new();
         for (path, config, regex) in onagre_launcher_toolkit::service::load::from_paths() {
             let icon: Option<IconPath> = THEME.icon_theme.as_ref().and_then(|theme| {
@@ -52,13 +88,46 @@ impl PluginConfigCache {
                 history: config.history,
                 help: config.query.help.map(|h| h.to_string()),
                 regex,
+                native: false,
             };
 
             cache.insert(name, plugin);
         }
 
+        // Built-in theme picker
+        let icon = THEME.icon_theme.as_ref().and_then(|theme| {
+            IconPath::lookup("preferences-desktop-theme", theme, THEME.icon_size)
+        });
+        cache.insert(
+            THEMES_MODE.to_string(),
+            Plugin {
+                name: THEMES_MODE.to_string(),
+                icon,
+                history: false,
+                help: Some(format!("{THEMES_MODE} ")),
+                regex: Regex::new(&format!("^({THEMES_MODE} )+")).ok(),
+                native: false,
+            },
+        );
+
+        for plugin in native_modes.plugins() {
+            cache.insert(plugin.name.clone(), plugin);
+        }
+
         PluginConfigCache { inner: cache }
     }
+
+    // Native modes come first, pop-launcher plugins may use the same prefix
+    pub fn try_match(&self, input: &str) -> Option<QueryData> {
+        let (native, plugins): (Vec<&Plugin>, Vec<&Plugin>) =
+            self.inner.values().partition(|plugin| plugin.native);
+
+        native
+            .into_iter()
+            .chain(plugins)
+            .find_map(|plugin| plugin.try_match(input))
+    }
+
     pub fn get_plugin_icon(&self, plugin_name: &str) -> Option<IconPath> {
         self.inner.get(plugin_name).and_then(|de| de.icon.clone())
     }
@@ -86,6 +155,10 @@ impl State<'_> {
         &self.input_value.mode
     }
 
+    pub fn shows_pop_results(&self) -> bool {
+        self.get_active_mode().shows_pop_results()
+    }
+
     pub fn get_input(&self) -> String {
         if THEME.plugin_hint().is_none() {
             self.input_value.input_display.clone()
@@ -95,14 +168,11 @@ impl State<'_> {
     }
 
     pub fn with_mode(mode_query: &str) -> Self {
-        let plugin_matchers = PluginConfigCache::load();
-        let plugin_split = match_web_plugins(mode_query).or_else(|| {
-            plugin_matchers
-                .inner
-                .values()
-                .map(|matcher| matcher.try_match(mode_query))
-                .find_map(|match_| match_)
-        });
+        let cache = Cache::default();
+        let native_modes = ModeRegistry::load(&cache.db);
+        let plugin_matchers = PluginConfigCache::load(&native_modes);
+        let plugin_split =
+            match_web_plugins(mode_query).or_else(|| plugin_matchers.try_match(mode_query));
 
         let mode = plugin_split
             .as_ref()
@@ -118,7 +188,7 @@ impl State<'_> {
 
         State {
             selected: Selection::History(0),
-            cache: Default::default(),
+            cache,
             pop_search: Default::default(),
             scroll: Default::default(),
             input_value: SearchInput {
@@ -129,9 +199,73 @@ impl State<'_> {
             },
             exec_on_next_search: false,
             plugin_matchers,
+            themes: themes::list(),
+            preview: None,
+            preview_source: None,
+            context_menu: None,
+            backend: BackendStatus::Starting,
+            native_modes,
+            native_search: vec![],
         }
     }
 
+    // The user query, without the mode modifier
+    pub fn mode_query(&self) -> &str {
+        let input = &self.input_value.input_display;
+        input
+            .strip_prefix(&self.input_value.modifier_display)
+            .unwrap_or(input)
+            .trim_start()
+    }
+
+    // Themes matching the user query, without the mode modifier
+    pub fn matching_themes(&self) -> Vec<&NamedTheme> {
+        let query = self.mode_query().trim_end().to_lowercase();
+
+        self.themes
+            .iter()
+            .filter(|theme| theme.name.to_lowercase().contains(&query))
+            .collect()
+    }
+
+    // Run the query of the active native mode
+    pub fn search_native(&mut self) {
+        self.native_search = match &self.input_value.mode {
+            ActiveMode::Native { mode_name, .. } => {
+                let query = self.mode_query().trim_end().to_string();
+                let Some(mode) = self.native_modes.get_mut(mode_name) else {
+                    return;
+                };
+
+                if !mode.history() {
+                    mode.query(&query)
+                } else {
+                    let history = self.cache.plugin_history(mode_name);
+                    let keys: Vec<&str> =
+                        history.iter().map(|entry| entry.query.as_ref()).collect();
+                    mode.load_history(&keys);
+
+                    if query.is_empty() {
+                        keys.iter()
+                            .filter_map(|key| mode.history_entry(key))
+                            .collect()
+                    } else {
+                        // History is sorted by weight, most used entries come first
+                        let mut entries = mode.query(&query);
+                        entries.sort_by_key(|entry| {
+                            let key = mode.history_key(entry);
+                            keys.iter()
+                                .position(|used| *used == key)
+                                .unwrap_or(keys.len())
+                        });
+                        entries
+                    }
+                }
+            }
+            _ => vec![],
+        };
+    }
+
     pub fn set_input(&mut self, input: &str) {
         let previous_modi = self.input_value.modifier_display.clone();
 
@@ -142,13 +276,13 @@ impl State<'_> {
         };
 
         let pop_query = match &self.input_value.mode {
-            ActiveMode::History | ActiveMode::DesktopEntry => {
+            ActiveMode::History | ActiveMode::DesktopEntry | ActiveMode::Themes => {
                 self.input_value.input_display.clone()
             }
             ActiveMode::Web { modifier, .. } => {
                 format!("{modifier} {}", self.input_value.input_display)
             }
-            ActiveMode::Plugin { modifier, .. } => {
+            ActiveMode::Plugin { modifier, .. } | ActiveMode::Native { modifier, .. } => {
                 format!("{modifier}{}", self.input_value.input_display)
             }
         };
@@ -161,13 +295,8 @@ impl State<'_> {
     }
 
     fn set_input_without_modifier(&mut self, input: &str) {
-        let plugin_split = match_web_plugins(input).or_else(|| {
-            self.plugin_matchers
-                .inner
-                .values()
-                .map(|matcher| matcher.try_match(input))
-                .find_map(|match_| match_)
-        });
+        let plugin_split =
+            match_web_plugins(input).or_else(|| self.plugin_matchers.try_match(input));
 
         if let Some(query_data) = plugin_split {
             self.input_value.modifier_display = query_data.modifier.clone();
@@ -182,10 +311,16 @@ impl State<'_> {
         } else {
             self.input_value.input_display = input.to_string();
 
-            if input.is_empty() {
-                self.input_value.mode = ActiveMode::History
+            self.input_value.mode = if input.is_empty() {
+                ActiveMode::History
+            } else if self.backend == BackendStatus::Unavailable {
+                // Without pop-launcher, applications come from the native index
+                ActiveMode::Native {
+                    mode_name: APPS_MODE.to_string(),
+                    modifier: String::new(),
+                }
             } else {
-                self.input_value.mode = ActiveMode::DesktopEntry
+                ActiveMode::DesktopEntry
             }
         }
     }
@@ -216,14 +351,23 @@ pub struct SearchInput {
 
 impl Default for State<'_> {
     fn default() -> Self {
+        let cache = Cache::default();
+        let native_modes = ModeRegistry::load(&cache.db);
         State {
             selected: Selection::History(0),
-            cache: Default::default(),
+            cache,
             pop_search: Default::default(),
             scroll: Default::default(),
             input_value: SearchInput::default(),
             exec_on_next_search: false,
-            plugin_matchers: PluginConfigCache::load(),
+            plugin_matchers: PluginConfigCache::load(&native_modes),
+            themes: themes::list(),
+            preview: None,
+            preview_source: None,
+            context_menu: None,
+            backend: BackendStatus::Starting,
+            native_modes,
+            native_search: vec![],
         }
     }
 }
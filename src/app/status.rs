@@ -0,0 +1,185 @@
+use crate::app::mode::ActiveMode;
+use crate::app::state::{BackendStatus, Selection, State};
+
+// Content of the status bar, derived from the application state
+#[derive(Debug, PartialEq)]
+pub struct StatusLine {
+    pub mode: String,
+    pub results: String,
+    // Only shown when the results come from pop-launcher
+    pub backend: Option<&'static str>,
+    // Key and action pairs
+    pub hints: Vec<(&'static str, &'static str)>,
+}
+
+// What Tab and the menu key can do with the selected result
+#[derive(Debug, Default, Clone, Copy)]
+struct SelectedActions {
+    complete: bool,
+    options: bool,
+}
+
+impl SelectedActions {
+    fn of(state: &State) -> Self {
+        let ActiveMode::Native { mode_name, .. } = state.get_active_mode() else {
+            let pop_results = state.shows_pop_results();
+            return SelectedActions {
+                complete: pop_results,
+                options: pop_results,
+            };
+        };
+
+        let entry = match state.selected {
+            Selection::History(idx) => state.native_search.get(idx),
+            _ => None,
+        };
+
+        match state.native_modes.get(mode_name).zip(entry) {
+            Some((mode, entry)) => SelectedActions {
+                complete: mode.complete(entry).is_some(),
+                options: !mode.context(entry).is_empty(),
+            },
+            None => SelectedActions::default(),
+        }
+    }
+}
+
+impl StatusLine {
+    // `shown` results are displayed out of `matching` when the theme caps them
+    pub fn new(state: &State, shown: usize, matching: usize) -> Self {
+        StatusLine::build(
+            state.get_active_mode(),
+            state.context_menu.is_some(),
+            SelectedActions::of(state),
+            state.backend,
+            shown,
+            matching,
+        )
+    }
+
+    fn build(
+        mode: &ActiveMode,
+        in_menu: bool,
+        actions: SelectedActions,
+        backend: BackendStatus,
+        shown: usize,
+        matching: usize,
+    ) -> Self {
+        let pop_results = mode.shows_pop_results() && !in_menu;
+
+        let mode = if in_menu {
+            "Options".to_string()
+        } else {
+            mode.label()
+        };
+
+        let plural = if matching == 1 { "" } else { "s" };
+        let results = if shown < matching {
+            format!("{shown} of {matching} result{plural}")
+        } else {
+            format!("{shown} result{plural}")
+        };
+
+        let backend = pop_results.then_some(match backend {
+            BackendStatus::Starting => "Starting…",
+            BackendStatus::Searching => "Searching…",
+            BackendStatus::Ready => "Ready",
+            BackendStatus::Unavailable => "pop-launcher unavailable",
+        });
+
+        let hints = if in_menu {
+            vec![("Enter", "activate"), ("Esc", "close")]
+        } else {
+            let mut hints = vec![("Enter", "launch")];
+            if actions.complete {
+                hints.push(("Tab", "complete"));
+            }
+            if actions.options {
+                hints.push(("Menu", "options"));
+            }
+            hints.push(("Esc", "quit"));
+            hints
+        };
+
+        StatusLine {
+            mode,
+            results,
+            backend,
+            hints,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::mode::ActiveMode;
+    use crate::app::state::BackendStatus;
+    use crate::app::status::{SelectedActions, StatusLine};
+    use speculoos::prelude::*;
+
+    fn pop_actions() -> SelectedActions {
+        SelectedActions {
+            complete: true,
+            options: true,
+        }
+    }
+
+    #[test]
+    fn should_describe_pop_launcher_search() {
+        let status = StatusLine::build(
+            &ActiveMode::DesktopEntry,
+            false,
+            pop_actions(),
+            BackendStatus::Searching,
+            20,
+            134,
+        );
+
+        assert_that!(status.mode).is_equal_to("Applications".to_string());
+        assert_that!(status.results).is_equal_to("20 of 134 results".to_string());
+        assert_that!(status.backend).is_equal_to(Some("Searching…"));
+        assert_that!(status.hints).contains(("Menu", "options"));
+    }
+
+    #[test]
+    fn should_describe_context_menu() {
+        let status = StatusLine::build(
+            &ActiveMode::DesktopEntry,
+            true,
+            pop_actions(),
+            BackendStatus::Ready,
+            1,
+            1,
+        );
+
+        assert_that!(status.mode).is_equal_to("Options".to_string());
+        assert_that!(status.results).is_equal_to("1 result".to_string());
+        assert_that!(status.backend).is_none();
+        assert_that!(status.hints).is_equal_to(vec![("Enter", "activate"), ("Esc", "close")]);
+    }
+
+    #[test]
+    fn should_hint_native_mode_actions() {
+        let status = StatusLine::build(
+            &ActiveMode::Native {
+                mode_name: "run".to_string(),
+                modifier: "!".to_string(),
+            },
+            false,
+            SelectedActions {
+                complete: true,
+                options: false,
+            },
+            BackendStatus::Unavailable,
+            3,
+            3,
+        );
+
+        assert_that!(status.backend).is_none();
+        assert_that!(status.hints).is_equal_to(vec![
+            ("Enter", "launch"),
+            ("Tab", "complete"),
+            ("Esc", "quit"),
+        ]);
+    }
+}
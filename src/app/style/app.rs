@@ -1,12 +1,26 @@
+use crate::app::style::preview::PreviewStyles;
 use crate::app::style::scrollable::scroller::ScrollerStyles;
 use crate::app::style::scrollable::RowContainerStyle;
 use crate::app::style::search::SearchContainerStyles;
+use crate::app::style::status::StatusStyles;
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
-use iced_core::border::Radius;
-use iced_core::{Background, Border};
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
+use iced_core::Border;
 use iced_style::container::{Appearance, StyleSheet};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+// The source of a `.rows[mode="..."]` or `@plugin` block
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModeRowsOverride {
+    pub source: String,
+    // Directory of the declaring file, relative paths are resolved against it
+    pub dir: Option<PathBuf>,
+}
 
 // The top level container wrapping the app
 // We don't want to edit this style, it's here only to provide rounded
@@ -16,11 +30,13 @@ pub struct AppWrapperStyle;
 #[derive(Debug, PartialEq)]
 pub struct AppContainerStyles {
     // Style
-    pub background: OnagreColor,
+    pub background: OnagreBackground,
+    pub background_image: Option<PathBuf>,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
-    pub border_radius: f32,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
+    pub box_shadow: OnagreShadow,
 
     // Layout
     pub padding: OnagrePadding,
@@ -28,7 +44,13 @@ pub struct AppContainerStyles {
     // Children
     pub search: SearchContainerStyles,
     pub rows: RowContainerStyle,
+    // Rows style overrides, by mode name
+    pub mode_rows: HashMap<String, RowContainerStyle>,
+    // Mode overrides declared while parsing, applied once the base rows style is final
+    pub mode_rows_overrides: Vec<ModeRowsOverride>,
     pub scrollable: ScrollerStyles,
+    pub preview: Option<PreviewStyles>,
+    pub status: Option<StatusStyles>,
 }
 
 impl Scale for AppContainerStyles {
@@ -36,9 +58,17 @@ impl Scale for AppContainerStyles {
         self.search = self.search.scale(scale);
         self.padding = self.padding * scale;
         self.rows = self.rows.scale(scale);
+        self.mode_rows = self
+            .mode_rows
+            .into_iter()
+            .map(|(mode, rows)| (mode, rows.scale(scale)))
+            .collect();
         self.search = self.search.scale(scale);
         self.scrollable = self.scrollable.scale(scale);
+        self.preview = self.preview.map(|preview| preview.scale(scale));
+        self.status = self.status.map(|status| status.scale(scale));
         self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
         self
     }
 }
@@ -49,13 +79,13 @@ impl StyleSheet for &AppContainerStyles {
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
             text_color: Some(self.color.into()),
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
-            shadow: Default::default(),
+            shadow: self.box_shadow.into(),
         }
     }
 }
@@ -63,15 +93,21 @@ impl StyleSheet for &AppContainerStyles {
 impl Default for AppContainerStyles {
     fn default() -> Self {
         Self {
-            background: OnagreColor::DEFAULT_BACKGROUND,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            background_image: None,
             color: OnagreColor::DEFAULT_TEXT,
             border_color: OnagreColor::RED,
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
             padding: OnagrePadding::from(20),
             search: Default::default(),
             rows: Default::default(),
+            mode_rows: Default::default(),
+            mode_rows_overrides: vec![],
             scrollable: Default::default(),
+            preview: None,
+            status: None,
         }
     }
 }
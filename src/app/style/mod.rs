@@ -1,27 +1,121 @@
+use crate::app::mode::ActiveMode;
 use crate::app::style::app::AppContainerStyles;
+use crate::app::style::preview::PreviewStyles;
 use crate::app::style::rows::generic::GenericContainerStyle;
 use crate::app::style::scrollable::scroller::ScrollerStyles;
+use crate::app::style::scrollable::RowContainerStyle;
 use crate::app::style::search::input::SearchInputStyles;
 use crate::app::style::search::SearchContainerStyles;
+use crate::app::style::status::StatusStyles;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::freedesktop::settings::ColorScheme;
 use crate::THEME_PATH;
 use crate::THEME_SCALE;
+use crate::TOUCH_MODE;
 use iced::widget::container::Appearance;
-use iced::Background;
-use iced_core::border::Radius;
 use iced_core::{Border, Length};
+use std::cell::Cell;
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
 use tracing::{error, warn};
 
 pub mod app;
+pub mod preview;
 pub mod rows;
 pub mod scrollable;
 pub mod search;
+pub mod status;
 
-impl Theme {
+// Loaded themes are never freed, styles borrow them for `'static`, background tasks
+// included, so the theme picker stops loading new ones past this limit
+const MAX_LOADED_THEMES: usize = 32;
+
+// The current theme, swapped to preview themes. Themes are leaked so styles can borrow them
+pub struct ThemeHandle {
+    current: RwLock<&'static Theme>,
+    loaded: Mutex<HashMap<PathBuf, &'static Theme>>,
+}
+
+impl ThemeHandle {
     pub fn load() -> Self {
-        let buf = THEME_PATH.lock().unwrap().clone();
-        let theme = crate::config::parse_file(&buf);
+        let path = THEME_PATH.lock().unwrap().clone();
+        let path = path.canonicalize().unwrap_or(path);
+        let theme: &'static Theme = Box::leak(Box::new(Theme::load_from(&path)));
+        ThemeHandle {
+            current: RwLock::new(theme),
+            loaded: Mutex::new(HashMap::from([(path, theme)])),
+        }
+    }
+
+    // Replace the current theme, loading it only once per path and color scheme
+    pub fn swap<P: AsRef<Path>>(&self, path: P) {
+        let path = path.as_ref();
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let mut loaded = self.loaded.lock().unwrap();
+        let theme = match loaded.get(&path) {
+            Some(theme) if !theme.is_stale() => *theme,
+            // A stale theme is replaced, this only leaks a theme per color scheme change
+            Some(_) => {
+                let theme: &'static Theme = Box::leak(Box::new(Theme::load_from(&path)));
+                loaded.insert(path.clone(), theme);
+                theme
+            }
+            None if loaded.len() >= MAX_LOADED_THEMES => {
+                warn!("{MAX_LOADED_THEMES} themes already loaded, not loading {path:?}");
+                return;
+            }
+            None => {
+                let theme: &'static Theme = Box::leak(Box::new(Theme::load_from(&path)));
+                loaded.insert(path.clone(), theme);
+                theme
+            }
+        };
+
+        *self.current.write().unwrap() = theme;
+        *THEME_PATH.lock().unwrap() = path;
+    }
+
+    // Detect the color scheme again and reload the current theme if it depends on it
+    pub fn refresh_color_scheme(&self) {
+        if self.color_scheme.is_none() {
+            return;
+        }
+
+        ColorScheme::refresh();
+        if self.is_stale() {
+            let path = THEME_PATH.lock().unwrap().clone();
+            self.swap(path);
+        }
+    }
+}
+
+impl Deref for ThemeHandle {
+    type Target = Theme;
+
+    fn deref(&self) -> &Self::Target {
+        *self.current.read().unwrap()
+    }
+}
+
+impl Theme {
+    pub fn load_from(path: &Path) -> Self {
+        // The color scheme is only detected for themes depending on it
+        let color_scheme = Cell::new(None);
+        let current_scheme = || {
+            let scheme = ColorScheme::current();
+            color_scheme.set(Some(scheme));
+            scheme
+        };
+
+        let buf = path.to_path_buf();
+        let buf = Theme::variant_path(&buf, current_scheme).unwrap_or(buf);
+        let theme = crate::config::parse_file_with_scheme(&buf, &current_scheme);
         if let Err(err) = &theme {
             error!("Failed to parse theme {buf:?}: {err}");
             warn!("Failing back to default theme");
@@ -32,10 +126,61 @@ impl Theme {
             theme = theme.scale(*scale)
         }
 
+        if TOUCH_MODE.get() == Some(&true) {
+            theme.touch = true;
+        }
+
+        if theme.touch {
+            theme = theme.with_touch_targets();
+        }
+
+        theme.color_scheme = color_scheme.get();
         theme
     }
+
+    // Whether the theme was loaded for another color scheme than the current one
+    fn is_stale(&self) -> bool {
+        self.color_scheme
+            .is_some_and(|scheme| scheme != ColorScheme::current())
+    }
+
+    // Rows and search bar are enlarged so they are easy to hit with a finger,
+    // taps always launch the tapped row
+    fn with_touch_targets(mut self) -> Self {
+        let app = &mut self.app_container;
+        app.rows = std::mem::take(&mut app.rows).scale(TOUCH_SCALE);
+        app.mode_rows = std::mem::take(&mut app.mode_rows)
+            .into_iter()
+            .map(|(mode, rows)| (mode, rows.scale(TOUCH_SCALE)))
+            .collect();
+        app.search = std::mem::take(&mut app.search).scale(TOUCH_SCALE);
+        self.hover_select = false;
+        self.click_activation = ClickActivation::Single;
+        self
+    }
+
+    // A `theme.dark.scss` or `theme.light.scss` file next to the theme
+    // takes precedence over it when matching the system color scheme
+    fn variant_path(path: &Path, color_scheme: impl FnOnce() -> ColorScheme) -> Option<PathBuf> {
+        let extension = path.extension()?.to_string_lossy();
+        let dark = path.with_extension(format!("dark.{extension}"));
+        let light = path.with_extension(format!("light.{extension}"));
+        if !dark.exists() && !light.exists() {
+            return None;
+        }
+
+        let variant = if color_scheme().is_dark() {
+            dark
+        } else {
+            light
+        };
+        variant.exists().then_some(variant)
+    }
 }
 
+// Scale applied to hit targets in the touch profile
+const TOUCH_SCALE: f32 = 1.5;
+
 pub(crate) trait Scale {
     fn scale(self, scale: f32) -> Self;
 }
@@ -46,9 +191,34 @@ impl AsRef<Theme> for Theme {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AppLayout {
+    // Search bar above the results
+    Vertical,
+    // Search bar and results on a single line
+    Bar,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ClickActivation {
+    // A click activates the clicked row
+    Single,
+    // A click selects the clicked row, a second click activates it
+    Double,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct Theme {
     // Layout
+    pub layout: AppLayout,
+    // Mouse interactions
+    pub hover_select: bool,
+    // The wheel moves the selection rather than scrolling the results
+    pub wheel_select: bool,
+    pub click_activation: ClickActivation,
+    // Touch profile
+    pub touch: bool,
+    pub on_screen_keyboard: bool,
     pub exit_unfocused: bool,
     pub size: (u32, u32),
     pub font: Option<String>,
@@ -58,11 +228,15 @@ pub struct Theme {
     pub padding: OnagrePadding,
 
     // Style
-    pub background: OnagreColor,
+    pub background: OnagreBackground,
+    pub background_image: Option<PathBuf>,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
-    pub border_radius: f32,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
+    pub transition_duration: Duration,
+    // The color scheme the theme was loaded for, if it has scheme dependent rules
+    pub color_scheme: Option<ColorScheme>,
 
     // Children
     pub app_container: AppContainerStyles,
@@ -121,11 +295,47 @@ impl Theme {
     pub fn app(&self) -> &AppContainerStyles {
         &self.app_container
     }
+
+    pub fn preview(&self) -> Option<&PreviewStyles> {
+        self.app_container.preview.as_ref()
+    }
+
+    pub fn status(&self) -> Option<&StatusStyles> {
+        self.app_container.status.as_ref()
+    }
+
+    // Width left for the rows container once the window and container paddings
+    // and the preview pane are removed
+    pub fn rows_available_width(&self) -> f32 {
+        let app_padding = &self.app_container.padding;
+        let width = self.size.0 as f32
+            - (self.padding.left + self.padding.right) as f32
+            - (app_padding.left + app_padding.right) as f32;
+
+        match self.preview() {
+            Some(preview) => width - preview.rows_width_reduction(width),
+            None => width,
+        }
+    }
+
+    // Rows style for the given mode, falling back to the base rows style
+    pub fn rows(&self, mode: &ActiveMode) -> &RowContainerStyle {
+        self.app_container
+            .mode_rows
+            .get(mode.style_name())
+            .unwrap_or(&self.app_container.rows)
+    }
 }
 
 impl Default for Theme {
     fn default() -> Self {
         Self {
+            layout: AppLayout::Vertical,
+            hover_select: false,
+            wheel_select: false,
+            click_activation: ClickActivation::Single,
+            touch: false,
+            on_screen_keyboard: false,
             exit_unfocused: false,
             size: (450, 300),
             font: None,
@@ -133,11 +343,14 @@ impl Default for Theme {
             // TODO: default icon theme ?
             icon_theme: Some("Papirus".to_string()),
             icon_size: 24,
-            background: OnagreColor::DEFAULT_BACKGROUND,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            background_image: None,
             color: OnagreColor::DEFAULT_TEXT,
             border_color: OnagreColor::TRANSPARENT,
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
+            transition_duration: Duration::ZERO,
+            color_scheme: None,
             padding: OnagrePadding::ZERO,
             app_container: AppContainerStyles::default(),
         }
@@ -149,11 +362,11 @@ impl iced::widget::container::StyleSheet for &Theme {
 
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
             text_color: Some(self.color.into()),
             shadow: Default::default(),
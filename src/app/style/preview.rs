@@ -0,0 +1,109 @@
+use iced::Length;
+use iced_core::Border;
+use iced_style::container::{Appearance, StyleSheet};
+
+use crate::app::style::rows::generic::GenericContainerStyle;
+use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
+use crate::config::color::OnagreColor;
+use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PreviewPosition {
+    // Next to the results
+    Right,
+    // Below the results
+    Bottom,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PreviewStyles {
+    // Style
+    pub background: OnagreBackground,
+    pub color: OnagreColor,
+    pub border_color: OnagreColor,
+    pub border_radius: OnagreRadius,
+    pub border_width: f32,
+    pub box_shadow: OnagreShadow,
+
+    // Layout
+    pub position: PreviewPosition,
+    pub padding: OnagrePadding,
+    pub spacing: u16,
+    pub width: Length,
+    pub height: Length,
+    pub font_size: u16,
+
+    // Children
+    pub title: GenericContainerStyle,
+}
+
+impl Scale for PreviewStyles {
+    fn scale(mut self, scale: f32) -> Self {
+        self.padding = self.padding.scale(scale);
+        self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
+        self.spacing = self.spacing.scale(scale);
+        self.width = self.width.scale(scale);
+        self.height = self.height.scale(scale);
+        self.font_size = self.font_size.scale(scale);
+        self.title = self.title.scale(scale);
+        self
+    }
+}
+
+impl Eq for PreviewStyles {}
+
+impl StyleSheet for &PreviewStyles {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _: &Self::Style) -> Appearance {
+        Appearance {
+            text_color: Some(self.color.into()),
+            background: Some(self.background.into()),
+            border: Border {
+                color: self.border_color.into(),
+                width: self.border_width,
+                radius: self.border_radius.into(),
+            },
+            shadow: self.box_shadow.into(),
+        }
+    }
+}
+
+impl Default for PreviewStyles {
+    fn default() -> Self {
+        PreviewStyles {
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            color: OnagreColor::DEFAULT_TEXT,
+            border_color: OnagreColor::RED,
+            border_radius: OnagreRadius::ZERO,
+            border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
+            position: PreviewPosition::Right,
+            padding: OnagrePadding::from(10),
+            spacing: 10,
+            width: Length::Fill,
+            height: Length::Fill,
+            font_size: 12,
+            title: GenericContainerStyle {
+                font_size: 16,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl PreviewStyles {
+    // Width taken from the rows when the preview sits next to them
+    pub fn rows_width_reduction(&self, available_width: f32) -> f32 {
+        match (self.position, self.width) {
+            (PreviewPosition::Bottom, _) => 0.0,
+            (PreviewPosition::Right, Length::Fixed(width)) => width,
+            // Rows and preview share the line
+            (PreviewPosition::Right, _) => available_width / 2.0,
+        }
+    }
+}
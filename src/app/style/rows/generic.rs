@@ -1,17 +1,18 @@
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
-use iced_core::border::Radius;
-use iced_core::{Background, Border};
+use iced_core::Border;
 use iced_style::container::{Appearance, StyleSheet};
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GenericContainerStyle {
     // Style
-    pub background: OnagreColor,
-    pub border_radius: f32,
+    pub background: OnagreBackground,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
@@ -39,12 +40,12 @@ impl Scale for GenericContainerStyle {
 impl Default for GenericContainerStyle {
     fn default() -> Self {
         GenericContainerStyle {
-            background: OnagreColor::DEFAULT_BACKGROUND,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
             color: OnagreColor::DEFAULT_TEXT,
             width: Length::Fill,
             height: Length::Shrink,
             border_color: OnagreColor::RED,
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
             padding: OnagrePadding::ZERO,
             align_x: Horizontal::Left,
@@ -60,11 +61,11 @@ impl StyleSheet for &GenericContainerStyle {
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
             text_color: Some(self.color.into()),
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
             shadow: Default::default(),
         }
@@ -1,14 +1,16 @@
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct IconStyle {
     // Style
-    pub background: OnagreColor,
-    pub border_radius: f32,
+    pub background: OnagreBackground,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
@@ -39,8 +41,8 @@ impl Default for IconStyle {
     fn default() -> Self {
         IconStyle {
             // Style
-            background: OnagreColor::DEFAULT_BACKGROUND,
-            border_radius: 0.0,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
             border_color: OnagreColor::TRANSPARENT,
             color: OnagreColor::DEFAULT_TEXT,
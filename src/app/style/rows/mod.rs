@@ -1,13 +1,16 @@
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
 use generic::GenericContainerStyle;
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
-use iced_core::border::Radius;
-use iced_core::{Background, Border};
+use iced_core::Border;
 use iced_style::container::{Appearance, StyleSheet};
 use icon::IconStyle;
+use std::time::Duration;
 
 pub mod button;
 pub mod generic;
@@ -24,13 +27,15 @@ pub struct RowStyles {
     pub align_y: Vertical,
 
     // Style
-    pub background: OnagreColor,
-    pub border_radius: f32,
+    pub background: OnagreBackground,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
+    pub box_shadow: OnagreShadow,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
     pub hide_description: bool,
     pub hide_category_icon: bool,
+    pub transition_duration: Duration,
 
     // Children
     pub title: GenericContainerStyle,
@@ -45,6 +50,7 @@ impl Scale for RowStyles {
         self.width = self.width.scale(scale);
         self.spacing = self.spacing.scale(scale);
         self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
         self.title = self.title.scale(scale);
         self.description = self.description.scale(scale);
         self.icon = self.icon.scale(scale);
@@ -58,13 +64,13 @@ impl StyleSheet for &RowStyles {
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
             text_color: Some(self.color.into()),
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
-            shadow: Default::default(),
+            shadow: self.box_shadow.into(),
         }
     }
 }
@@ -74,16 +80,18 @@ impl Default for RowStyles {
         RowStyles {
             width: Length::Fill,
             height: Length::Shrink,
-            background: OnagreColor::DEFAULT_BACKGROUND,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
             color: OnagreColor::DEFAULT_TEXT,
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
             padding: OnagrePadding::from(5),
             align_x: Horizontal::Right,
             align_y: Vertical::Bottom,
             border_color: OnagreColor::RED,
             hide_description: false,
             hide_category_icon: false,
+            transition_duration: Duration::ZERO,
             title: GenericContainerStyle::default(),
             description: GenericContainerStyle::description_default(),
             icon: Default::default(),
@@ -110,3 +118,39 @@ impl RowStyles {
     }
 }
 impl Eq for RowStyles {}
+
+// A row style transitioning from `from` to `to`, `progress` goes from 0.0 to 1.0
+pub struct RowTransitionStyle {
+    pub from: &'static RowStyles,
+    pub to: &'static RowStyles,
+    pub progress: f32,
+}
+
+impl StyleSheet for RowTransitionStyle {
+    type Style = iced::Theme;
+
+    fn appearance(&self, style: &Self::Style) -> Appearance {
+        let from = self.from.appearance(style);
+        let to = self.to.appearance(style);
+        let nearest = if self.progress >= 0.5 { &to } else { &from };
+        Appearance {
+            text_color: Some(self.to.color.mix(self.from.color, self.progress).into()),
+            background: Some(
+                self.to
+                    .background
+                    .mix(self.from.background, self.progress)
+                    .into(),
+            ),
+            border: Border {
+                color: self
+                    .to
+                    .border_color
+                    .mix(self.from.border_color, self.progress)
+                    .into(),
+                width: from.border.width + (to.border.width - from.border.width) * self.progress,
+                radius: nearest.border.radius,
+            },
+            shadow: nearest.shadow,
+        }
+    }
+}
@@ -1,27 +1,43 @@
 use crate::app::style::rows::RowStyles;
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
 use iced::Length;
-use iced_core::border::Radius;
-use iced_core::{Background, Border};
+use iced_core::Border;
 use iced_style::container::{Appearance, StyleSheet};
+use std::path::PathBuf;
 
 pub mod scroller;
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RowsLayout {
+    // One entry per line
+    List,
+    // Icon above title cells
+    Grid,
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub struct RowContainerStyle {
     // Iced Container
     pub color: OnagreColor,
-    pub background: OnagreColor,
+    pub background: OnagreBackground,
+    pub background_image: Option<PathBuf>,
     pub border_color: OnagreColor,
-    pub border_radius: f32,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
+    pub box_shadow: OnagreShadow,
 
     // Layout
     pub padding: OnagrePadding,
     pub width: Length,
     pub height: Length,
+    pub layout: RowsLayout,
+    pub columns: Option<u16>,
+    pub cell_width: Option<u16>,
 
     // Iced Scrollable
     pub row: RowStyles,
@@ -32,8 +48,10 @@ impl Scale for RowContainerStyle {
     fn scale(mut self, scale: f32) -> Self {
         self.padding = self.padding.scale(scale);
         self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
         self.width = self.width.scale(scale);
         self.height = self.height.scale(scale);
+        self.cell_width = self.cell_width.map(|width| width.scale(scale));
         self.row = self.row.scale(scale);
         self.row_selected = self.row_selected.scale(scale);
         self
@@ -42,19 +60,62 @@ impl Scale for RowContainerStyle {
 
 impl Eq for RowContainerStyle {}
 
+impl RowContainerStyle {
+    // Default columns count when neither `--columns` nor `--cell-width` are set
+    const DEFAULT_COLUMNS: u16 = 4;
+
+    // Height of a row before it is laid out, rows without a fixed height
+    // are at least as high as their title
+    pub fn estimated_row_height(&self) -> f32 {
+        let row = &self.row;
+        match row.height {
+            Length::Fixed(height) => height,
+            _ => {
+                let title = &row.title;
+                title.font_size as f32 * 1.3
+                    + (title.padding.top + title.padding.bottom) as f32
+                    + (row.padding.top + row.padding.bottom) as f32
+            }
+        }
+    }
+
+    // Number of results displayed at once in the bar layout
+    pub fn bar_items(&self) -> usize {
+        self.columns.unwrap_or(Self::DEFAULT_COLUMNS).max(1) as usize
+    }
+
+    // Number of grid columns, either explicit or derived from the cell width
+    // and the space available in the window
+    pub fn grid_columns(&self, available_width: f32) -> usize {
+        let available_width = match self.width {
+            Length::Fixed(width) => width,
+            _ => available_width,
+        } - self.padding.left as f32
+            - self.padding.right as f32;
+
+        let columns = match (self.columns, self.cell_width) {
+            (Some(columns), _) => columns,
+            (None, Some(cell_width)) => (available_width / cell_width.max(1) as f32) as u16,
+            (None, None) => Self::DEFAULT_COLUMNS,
+        };
+
+        columns.max(1) as usize
+    }
+}
+
 impl StyleSheet for &RowContainerStyle {
     type Style = iced::Theme;
 
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
             text_color: Some(self.color.into()),
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
-            shadow: Default::default(),
+            shadow: self.box_shadow.into(),
         }
     }
 }
@@ -63,10 +124,12 @@ impl Default for RowContainerStyle {
     fn default() -> Self {
         Self {
             color: OnagreColor::DEFAULT_TEXT,
-            background: OnagreColor::DEFAULT_BACKGROUND,
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            background_image: None,
             border_color: OnagreColor::RED,
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
             padding: OnagrePadding {
                 top: 10,
                 right: 0,
@@ -75,6 +138,9 @@ impl Default for RowContainerStyle {
             },
             width: Length::Fill,
             height: Length::FillPortion(8),
+            layout: RowsLayout::List,
+            columns: None,
+            cell_width: None,
             row: RowStyles::default(),
             row_selected: RowStyles::default_selected(),
         }
@@ -1,25 +1,27 @@
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
+use crate::config::radius::OnagreRadius;
 use iced::widget::scrollable::Scrollbar;
-use iced::Background;
-use iced_core::border::Radius;
 use iced_core::Border;
 use iced_style::scrollable::{Appearance, StyleSheet};
 use iced_style::theme::Scrollable;
+use std::time::Duration;
 
 #[derive(Debug, PartialEq)]
 pub struct ScrollerStyles {
-    pub background: OnagreColor,
+    pub background: OnagreBackground,
     pub border_color: OnagreColor,
-    pub border_radius: f32,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
     pub scroller_color: OnagreColor,
-    pub scroller_border_radius: f32,
+    pub scroller_border_radius: OnagreRadius,
     pub scroller_border_width: f32,
     pub scroller_border_color: OnagreColor,
     pub scrollbar_margin: u16,
     pub scrollbar_width: u16,
     pub scroller_width: u16,
+    pub transition_duration: Duration,
 }
 
 impl Scale for ScrollerStyles {
@@ -38,17 +40,18 @@ impl Eq for ScrollerStyles {}
 impl Default for ScrollerStyles {
     fn default() -> Self {
         ScrollerStyles {
-            background: OnagreColor::DEFAULT_SCROLL,
-            border_radius: 0.3,
+            background: OnagreBackground::DEFAULT_SCROLL,
+            border_radius: OnagreRadius::from(0.3),
             border_width: 0.0,
             border_color: OnagreColor::TRANSPARENT,
             scroller_color: OnagreColor::DEFAULT_SCROLLER,
-            scroller_border_radius: 3.0,
+            scroller_border_radius: OnagreRadius::from(3.0),
             scroller_border_width: 0.0,
             scroller_border_color: OnagreColor::DEFAULT_BORDER,
             scrollbar_margin: 0,
             scrollbar_width: 4,
             scroller_width: 6,
+            transition_duration: Duration::ZERO,
         }
     }
 }
@@ -66,18 +69,18 @@ impl StyleSheet for &ScrollerStyles {
         Appearance {
             container: Default::default(),
             scrollbar: Scrollbar {
-                background: Some(Background::Color(self.background.into())),
+                background: Some(self.background.into()),
                 border: Border {
                     color: self.border_color.into(),
                     width: self.border_width,
-                    radius: Radius::from(self.border_radius),
+                    radius: self.border_radius.into(),
                 },
                 scroller: iced::widget::scrollable::Scroller {
                     color: self.scroller_color.into(),
                     border: Border {
                         color: self.scroller_border_color.into(),
                         width: self.scroller_border_width,
-                        radius: Radius::from(self.scroller_border_radius),
+                        radius: self.scroller_border_radius.into(),
                     },
                 },
             },
@@ -1,17 +1,18 @@
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
-use iced_core::border::Radius;
-use iced_core::{Background, Border, Color};
+use iced_core::{Border, Color};
 use iced_style::text_input::{Appearance, StyleSheet};
 
 #[derive(Debug, PartialEq)]
 pub struct SearchInputStyles {
     // Style
-    pub background: OnagreColor,
-    pub border_radius: f32,
+    pub background: OnagreBackground,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
     pub border_color: OnagreColor,
     pub placeholder_color: OnagreColor,
@@ -46,11 +47,11 @@ impl StyleSheet for &SearchInputStyles {
 
     fn active(&self, _: &Self::Style) -> Appearance {
         Appearance {
-            background: Background::Color(self.background.into()),
+            background: self.background.into(),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
             icon_color: Default::default(),
         }
@@ -78,11 +79,11 @@ impl StyleSheet for &SearchInputStyles {
 
     fn disabled(&self, _style: &Self::Style) -> Appearance {
         Appearance {
-            background: Background::Color(self.background.into()),
+            background: self.background.into(),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
             icon_color: Default::default(),
         }
@@ -92,10 +93,10 @@ impl StyleSheet for &SearchInputStyles {
 impl Default for SearchInputStyles {
     fn default() -> Self {
         SearchInputStyles {
-            border_radius: 0.0,
+            border_radius: OnagreRadius::ZERO,
             border_width: 0.0,
             border_color: OnagreColor::TRANSPARENT,
-            background: OnagreColor::WHITE,
+            background: OnagreBackground::WHITE,
             placeholder_color: OnagreColor::DEFAULT_TEXT,
             value_color: OnagreColor::DEFAULT_TEXT,
             selection_color: OnagreColor::DEFAULT_BORDER,
@@ -1,15 +1,18 @@
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
-use iced_core::border::Radius;
-use iced_core::{Background, Border};
+use iced_core::Border;
 use iced_style::container::{Appearance, StyleSheet};
+use std::path::PathBuf;
 
 use input::SearchInputStyles;
 
 use crate::app::style::rows::generic::GenericContainerStyle;
 use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
 use crate::config::color::OnagreColor;
 use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
 
 pub mod hint;
 pub mod input;
@@ -17,11 +20,13 @@ pub mod input;
 #[derive(Debug, PartialEq)]
 pub struct SearchContainerStyles {
     // Style
-    pub background: OnagreColor,
+    pub background: OnagreBackground,
+    pub background_image: Option<PathBuf>,
     pub color: OnagreColor,
     pub border_color: OnagreColor,
-    pub border_radius: f32,
+    pub border_radius: OnagreRadius,
     pub border_width: f32,
+    pub box_shadow: OnagreShadow,
 
     // Layout
     pub padding: OnagrePadding,
@@ -40,6 +45,7 @@ impl Scale for SearchContainerStyles {
     fn scale(mut self, scale: f32) -> Self {
         self.padding = self.padding.scale(scale);
         self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
         self.spacing = self.spacing.scale(scale);
         self.width = self.width.scale(scale);
         self.height = self.height.scale(scale);
@@ -56,13 +62,13 @@ impl StyleSheet for &SearchContainerStyles {
     fn appearance(&self, _: &Self::Style) -> Appearance {
         Appearance {
             text_color: Some(self.color.into()),
-            background: Some(Background::Color(self.background.into())),
+            background: Some(self.background.into()),
             border: Border {
                 color: self.border_color.into(),
                 width: self.border_width,
-                radius: Radius::from(self.border_radius),
+                radius: self.border_radius.into(),
             },
-            shadow: Default::default(),
+            shadow: self.box_shadow.into(),
         }
     }
 }
@@ -70,10 +76,12 @@ impl StyleSheet for &SearchContainerStyles {
 impl Default for SearchContainerStyles {
     fn default() -> Self {
         Self {
-            background: OnagreColor::WHITE,
+            background: OnagreBackground::WHITE,
+            background_image: None,
             color: OnagreColor::DEFAULT_TEXT,
-            border_radius: 4.0,
+            border_radius: OnagreRadius::from(4.0),
             border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
             border_color: OnagreColor::TRANSPARENT,
             align_x: Horizontal::Left,
             align_y: Vertical::Center,
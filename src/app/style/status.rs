@@ -0,0 +1,85 @@
+use iced::alignment::Vertical;
+use iced::Length;
+use iced_core::Border;
+use iced_style::container::{Appearance, StyleSheet};
+
+use crate::app::style::Scale;
+use crate::config::background::OnagreBackground;
+use crate::config::color::OnagreColor;
+use crate::config::padding::OnagrePadding;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct StatusStyles {
+    // Style
+    pub background: OnagreBackground,
+    pub color: OnagreColor,
+    pub border_color: OnagreColor,
+    pub border_radius: OnagreRadius,
+    pub border_width: f32,
+    pub box_shadow: OnagreShadow,
+
+    // Layout
+    pub padding: OnagrePadding,
+    pub spacing: u16,
+    pub width: Length,
+    pub height: Length,
+    pub align_y: Vertical,
+    pub font_size: u16,
+
+    // Content
+    pub key_hints: bool,
+}
+
+impl Scale for StatusStyles {
+    fn scale(mut self, scale: f32) -> Self {
+        self.padding = self.padding.scale(scale);
+        self.border_width = self.border_width.scale(scale);
+        self.box_shadow = self.box_shadow.scale(scale);
+        self.spacing = self.spacing.scale(scale);
+        self.width = self.width.scale(scale);
+        self.height = self.height.scale(scale);
+        self.font_size = self.font_size.scale(scale);
+        self
+    }
+}
+
+impl Eq for StatusStyles {}
+
+impl StyleSheet for &StatusStyles {
+    type Style = iced::Theme;
+
+    fn appearance(&self, _: &Self::Style) -> Appearance {
+        Appearance {
+            text_color: Some(self.color.into()),
+            background: Some(self.background.into()),
+            border: Border {
+                color: self.border_color.into(),
+                width: self.border_width,
+                radius: self.border_radius.into(),
+            },
+            shadow: self.box_shadow.into(),
+        }
+    }
+}
+
+impl Default for StatusStyles {
+    fn default() -> Self {
+        StatusStyles {
+            background: OnagreBackground::DEFAULT_BACKGROUND,
+            color: OnagreColor::DEFAULT_TEXT,
+            border_color: OnagreColor::RED,
+            border_radius: OnagreRadius::ZERO,
+            border_width: 0.0,
+            box_shadow: OnagreShadow::NONE,
+            padding: OnagrePadding::from(4),
+            spacing: 20,
+            width: Length::Fill,
+            height: Length::Shrink,
+            align_y: Vertical::Center,
+            font_size: 12,
+            key_hints: true,
+        }
+    }
+}
@@ -8,7 +8,7 @@ use iced_runtime::futures::futures::stream;
 use iced_runtime::futures::subscription::Recipe;
 use onagre_launcher_toolkit::launcher::{json_input_stream, Request, Response};
 use std::hash::Hash;
-use std::process::{exit, Stdio};
+use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{ChildStderr, ChildStdin, ChildStdout, Command};
 use tracing::{debug, error};
@@ -54,6 +54,8 @@ pub struct PopLauncherSubscription;
 #[derive(Debug, Clone)]
 pub enum SubscriptionMessage {
     Ready(Sender<Request>),
+    // pop-launcher could not be started
+    Unavailable,
     PopMessage(Response),
 }
 
@@ -82,7 +84,8 @@ impl Recipe for PopLauncherSubscription {
             error!("Failed to start pop-launcher backend.");
             error!("Make sure either pop-launcher or onagre-launcher is installed.");
             error!("See: https://github.com/pop-os/launcher or https://github.com/onagre-launcher/launcher");
-            exit(1);
+            error!("Only native modes are available.");
+            return Box::pin(stream::iter(vec![SubscriptionMessage::Unavailable]));
         };
 
         let (response_tx, response_rx) = channel(32);
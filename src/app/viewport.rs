@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+// Lines rendered above and below the visible ones, so fast scrolling
+// does not show empty space before the next redraw
+const OVERSCAN: usize = 3;
+
+// Measured result heights and the visible part of the results, so only
+// visible rows are built and the selection can be scrolled into view
+#[derive(Debug)]
+pub struct ResultsViewport {
+    // Result heights, measured when laid out
+    heights: HashMap<usize, f32>,
+    // Height used for results not measured yet
+    estimate: f32,
+    // Absolute scroll offset
+    offset: f32,
+    // Visible height of the results
+    height: f32,
+}
+
+// Results to build, and the space taken by the lines before and after them
+#[derive(Debug, PartialEq)]
+pub struct VisibleResults {
+    pub range: Range<usize>,
+    pub space_before: f32,
+    pub space_after: f32,
+}
+
+impl ResultsViewport {
+    pub fn new(estimate: f32, height: f32) -> Self {
+        ResultsViewport {
+            heights: Default::default(),
+            estimate,
+            offset: 0.0,
+            height,
+        }
+    }
+
+    pub fn record(&mut self, idx: usize, height: f32) {
+        self.heights.insert(idx, height);
+    }
+
+    pub fn measured(&self, idx: usize) -> Option<f32> {
+        self.heights.get(&idx).copied()
+    }
+
+    pub fn set_height(&mut self, height: f32) {
+        self.height = height;
+    }
+
+    pub fn height(&self) -> f32 {
+        self.height
+    }
+
+    pub fn on_scrolled(&mut self, offset: f32) {
+        self.offset = offset;
+    }
+
+    pub fn offset(&self) -> f32 {
+        self.offset
+    }
+
+    // The offset at which the last line is at the bottom of the viewport
+    pub fn max_offset(&self, total: usize, columns: usize) -> f32 {
+        let content_height: f32 = self.line_heights(columns, total).iter().sum();
+        (content_height - self.height).max(0.0)
+    }
+
+    // Results changed, previous measures are meaningless
+    pub fn reset(&mut self, estimate: f32) {
+        self.heights.clear();
+        self.estimate = estimate;
+    }
+
+    // A line of the grid is as high as its highest cell, lists have one result per line
+    fn line_height(&self, line: usize, columns: usize, total: usize) -> f32 {
+        let start = line * columns;
+        (start..(start + columns).min(total))
+            .map(|idx| self.measured(idx).unwrap_or(self.estimate))
+            .fold(0.0, f32::max)
+    }
+
+    fn line_heights(&self, columns: usize, total: usize) -> Vec<f32> {
+        (0..total.div_ceil(columns))
+            .map(|line| self.line_height(line, columns, total))
+            .collect()
+    }
+
+    pub fn visible(&self, total: usize, columns: usize) -> VisibleResults {
+        let lines = self.line_heights(columns, total);
+        let bottom = self.offset + self.height;
+
+        let mut top = 0.0;
+        let mut first = None;
+        let mut last = 0;
+        for (line, height) in lines.iter().enumerate() {
+            if top + height > self.offset && first.is_none() {
+                first = Some(line);
+            }
+            if top < bottom {
+                last = line;
+            }
+            top += height;
+        }
+
+        let first = first.unwrap_or(0).saturating_sub(OVERSCAN);
+        let end = (last + 1 + OVERSCAN).min(lines.len());
+
+        VisibleResults {
+            range: (first * columns)..(end * columns).min(total),
+            space_before: lines[..first].iter().sum(),
+            space_after: lines[end..].iter().sum(),
+        }
+    }
+
+    // The offset showing the selected result, if it is not visible already
+    pub fn scroll_target(&self, selected: usize, columns: usize, total: usize) -> Option<f32> {
+        let lines = self.line_heights(columns, total);
+        let line = selected / columns;
+        let top: f32 = lines.get(..line)?.iter().sum();
+        let bottom = top + lines.get(line)?;
+        let height = self.height;
+
+        if top < self.offset {
+            Some(top)
+        } else if bottom > self.offset + height {
+            Some((bottom - height).max(0.0))
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::app::viewport::ResultsViewport;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_only_build_visible_results() {
+        let mut viewport = ResultsViewport::new(20.0, 100.0);
+        viewport.on_scrolled(400.0);
+
+        let visible = viewport.visible(100, 1);
+
+        // Lines 20 to 24 are visible, plus the overscan
+        assert_that!(visible.range).is_equal_to(17..28);
+        assert_that!(visible.space_before).is_equal_to(340.0);
+        assert_that!(visible.space_after).is_equal_to(1440.0);
+    }
+
+    #[test]
+    fn should_scroll_to_measured_rows() {
+        let mut viewport = ResultsViewport::new(20.0, 100.0);
+        viewport.record(0, 60.0);
+        viewport.record(1, 60.0);
+
+        // The third row ends at 140px, below the 100px viewport
+        assert_that!(viewport.scroll_target(2, 1, 10)).is_equal_to(Some(40.0));
+        assert_that!(viewport.scroll_target(0, 1, 10)).is_none();
+    }
+
+    #[test]
+    fn should_scroll_by_grid_lines() {
+        let mut viewport = ResultsViewport::new(50.0, 100.0);
+        viewport.on_scrolled(100.0);
+
+        // The first cell of the first line is above the viewport
+        assert_that!(viewport.scroll_target(1, 4, 20)).is_equal_to(Some(0.0));
+        assert_that!(viewport.visible(20, 4).range).is_equal_to(0..20);
+    }
+}
@@ -0,0 +1,50 @@
+use iced::widget::image::{self, FilterMethod};
+use iced::{ContentFit, Element};
+use iced_core::layout::Layout;
+use std::path::Path;
+
+use crate::app::widgets::wrapper::{Hook, Wrapper};
+
+// Draw an image below its content, the image covers the whole content bounds.
+// The content keeps its own layout, background colors are drawn over the image
+// so they should be transparent for the image to be visible.
+pub struct BackgroundImage {
+    handle: image::Handle,
+}
+
+impl BackgroundImage {
+    pub fn new<'a, Message>(
+        content: impl Into<Element<'a, Message>>,
+        path: &Path,
+    ) -> Wrapper<'a, Message, Self> {
+        Wrapper::new(
+            content,
+            BackgroundImage {
+                handle: image::Handle::from_path(path),
+            },
+        )
+    }
+}
+
+// Wrap the content with a background image if any
+pub fn with_background_image<'a, Message: 'a>(
+    content: impl Into<Element<'a, Message>>,
+    path: Option<&Path>,
+) -> Element<'a, Message> {
+    match path {
+        Some(path) => BackgroundImage::new(content, path).into(),
+        None => content.into(),
+    }
+}
+
+impl<Message> Hook<Message> for BackgroundImage {
+    fn draw(&self, renderer: &mut iced::Renderer, layout: Layout<'_>) {
+        image::draw(
+            renderer,
+            layout,
+            &self.handle,
+            ContentFit::Cover,
+            FilterMethod::Linear,
+        );
+    }
+}
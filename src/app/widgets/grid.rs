@@ -0,0 +1,32 @@
+use iced::widget::{Column, Row, Space};
+use iced::{Element, Length};
+
+// Lay cells out in rows of `columns` cells, the last row is padded
+// with empty space so cells keep the same width
+pub fn grid<'a, Message: 'a>(
+    cells: Vec<Element<'a, Message>>,
+    columns: usize,
+) -> Column<'a, Message> {
+    let mut grid = Column::new().width(Length::Fill);
+    let mut row = Row::new().width(Length::Fill);
+    let mut len = 0;
+
+    for cell in cells {
+        row = row.push(cell);
+        len += 1;
+        if len == columns {
+            grid = grid.push(row);
+            row = Row::new().width(Length::Fill);
+            len = 0;
+        }
+    }
+
+    if len > 0 {
+        for _ in len..columns {
+            row = row.push(Space::with_width(Length::Fill));
+        }
+        grid = grid.push(row);
+    }
+
+    grid
+}
@@ -0,0 +1,48 @@
+use iced::{event, mouse, window, Element, Event};
+use iced_core::layout::Layout;
+use iced_core::Shell;
+
+use crate::app::widgets::wrapper::{Hook, Wrapper};
+
+// Publish the height of the content before it is drawn, when it differs
+// from the known one. The content is otherwise left untouched.
+pub struct Measured<'a, Message> {
+    known: Option<f32>,
+    on_measure: Box<dyn Fn(f32) -> Message + 'a>,
+}
+
+impl<'a, Message> Measured<'a, Message> {
+    pub fn new(
+        content: impl Into<Element<'a, Message>>,
+        known: Option<f32>,
+        on_measure: impl Fn(f32) -> Message + 'a,
+    ) -> Wrapper<'a, Message, Self> {
+        Wrapper::new(
+            content,
+            Measured {
+                known,
+                on_measure: Box::new(on_measure),
+            },
+        )
+    }
+}
+
+impl<Message> Hook<Message> for Measured<'_, Message> {
+    fn on_event(
+        &mut self,
+        event: &Event,
+        layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        // Redraws follow each layout, comparing with the known height avoids update loops
+        if let Event::Window(_, window::Event::RedrawRequested(_)) = event {
+            let height = layout.bounds().height;
+            if self.known != Some(height) {
+                shell.publish((self.on_measure)(height));
+            }
+        }
+
+        event::Status::Ignored
+    }
+}
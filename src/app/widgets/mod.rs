@@ -0,0 +1,6 @@
+pub mod background_image;
+pub mod grid;
+pub mod measured;
+pub mod touch;
+pub mod wheel;
+pub mod wrapper;
@@ -0,0 +1,273 @@
+use iced::{event, mouse, touch, Element, Event, Length, Point, Rectangle, Size, Vector};
+use iced_core::layout::{self, Layout};
+use iced_core::renderer;
+use iced_core::widget::{tree, Operation, Tree};
+use iced_core::{overlay, Clipboard, Shell, Widget};
+use std::time::{Duration, Instant};
+
+// Distance a finger can move before a tap becomes a drag
+const TAP_SLOP: f32 = 10.0;
+// Horizontal distance of a swipe
+const SWIPE_DISTANCE: f32 = 120.0;
+// A finger resting longer than this before being lifted does not fling
+const FLING_TIMEOUT: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Gesture {
+    // Vertical finger movement in pixels, positive when the content should scroll down
+    Drag(f32),
+    // Finger lifted while dragging, with the drag velocity in pixels per second
+    Fling(f32),
+    // Horizontal swipe
+    Swipe,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    finger: Option<touch::Finger>,
+    start: Point,
+    last: Point,
+    last_moved_at: Option<Instant>,
+    velocity: f32,
+    dragging: bool,
+}
+
+// Turn touch interactions on the content into gestures. Taps are forwarded
+// to the content once the finger is lifted, so a drag never presses a button.
+pub struct TouchGestures<'a, Message> {
+    content: Element<'a, Message>,
+    on_gesture: Box<dyn Fn(Gesture) -> Message + 'a>,
+}
+
+impl<'a, Message> TouchGestures<'a, Message> {
+    pub fn new(
+        content: impl Into<Element<'a, Message>>,
+        on_gesture: impl Fn(Gesture) -> Message + 'a,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            on_gesture: Box::new(on_gesture),
+        }
+    }
+}
+
+impl<Message> Widget<Message, iced::Theme, iced::Renderer> for TouchGestures<'_, Message> {
+    fn tag(&self) -> tree::Tag {
+        tree::Tag::of::<State>()
+    }
+
+    fn state(&self) -> tree::State {
+        tree::State::new(State::default())
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        vec![Tree::new(&self.content)]
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        tree.diff_children(std::slice::from_ref(&self.content));
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content
+            .as_widget()
+            .layout(&mut tree.children[0], renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(&mut tree.children[0], layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &iced::Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        let state = tree.state.downcast_mut::<State>();
+
+        match event {
+            Event::Touch(touch::Event::FingerPressed { id, position })
+                if state.finger.is_none() && layout.bounds().contains(position) =>
+            {
+                *state = State {
+                    finger: Some(id),
+                    start: position,
+                    last: position,
+                    last_moved_at: Some(Instant::now()),
+                    velocity: 0.0,
+                    dragging: false,
+                };
+
+                event::Status::Captured
+            }
+            Event::Touch(touch::Event::FingerMoved { id, position })
+                if state.finger == Some(id) =>
+            {
+                let now = Instant::now();
+                if !state.dragging && state.start.distance(position) > TAP_SLOP {
+                    state.dragging = true;
+                }
+
+                if state.dragging {
+                    let delta = state.last.y - position.y;
+                    let elapsed = state
+                        .last_moved_at
+                        .map(|at| now.duration_since(at).as_secs_f32())
+                        .unwrap_or_default();
+
+                    if elapsed > 0.0 {
+                        // Smooth the velocity, touch events are noisy
+                        state.velocity = 0.8 * (delta / elapsed) + 0.2 * state.velocity;
+                    }
+
+                    if delta != 0.0 {
+                        shell.publish((self.on_gesture)(Gesture::Drag(delta)));
+                    }
+                }
+
+                state.last = position;
+                state.last_moved_at = Some(now);
+                event::Status::Captured
+            }
+            Event::Touch(touch::Event::FingerLifted { id, position })
+                if state.finger == Some(id) =>
+            {
+                state.finger = None;
+                let dx = position.x - state.start.x;
+                let dy = position.y - state.start.y;
+
+                if dx.abs() > SWIPE_DISTANCE && dx.abs() > 2.0 * dy.abs() {
+                    shell.publish((self.on_gesture)(Gesture::Swipe));
+                } else if state.dragging {
+                    let resting = state
+                        .last_moved_at
+                        .map(|at| at.elapsed() > FLING_TIMEOUT)
+                        .unwrap_or(true);
+                    let velocity = if resting { 0.0 } else { state.velocity };
+                    shell.publish((self.on_gesture)(Gesture::Fling(velocity)));
+                } else {
+                    // A tap, replay it for the content
+                    let start = state.start;
+                    let content = self.content.as_widget_mut();
+                    let tree = &mut tree.children[0];
+                    for (event, position) in [
+                        (
+                            touch::Event::FingerPressed {
+                                id,
+                                position: start,
+                            },
+                            start,
+                        ),
+                        (touch::Event::FingerLifted { id, position }, position),
+                    ] {
+                        content.on_event(
+                            tree,
+                            Event::Touch(event),
+                            layout,
+                            mouse::Cursor::Available(position),
+                            renderer,
+                            clipboard,
+                            shell,
+                            viewport,
+                        );
+                    }
+                }
+
+                event::Status::Captured
+            }
+            Event::Touch(touch::Event::FingerLost { id, .. }) if state.finger == Some(id) => {
+                state.finger = None;
+                event::Status::Captured
+            }
+            _ => self.content.as_widget_mut().on_event(
+                &mut tree.children[0],
+                event,
+                layout,
+                cursor,
+                renderer,
+                clipboard,
+                shell,
+                viewport,
+            ),
+        }
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        self.content.as_widget().mouse_interaction(
+            &tree.children[0],
+            layout,
+            cursor,
+            viewport,
+            renderer,
+        )
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.content.as_widget().draw(
+            &tree.children[0],
+            renderer,
+            theme,
+            style,
+            layout,
+            cursor,
+            viewport,
+        );
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, iced::Theme, iced::Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(&mut tree.children[0], layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a> From<TouchGestures<'a, Message>> for Element<'a, Message> {
+    fn from(touch_gestures: TouchGestures<'a, Message>) -> Self {
+        Element::new(touch_gestures)
+    }
+}
@@ -0,0 +1,53 @@
+use iced::mouse::ScrollDelta;
+use iced::{event, mouse, Element, Event};
+use iced_core::layout::Layout;
+use iced_core::Shell;
+
+use crate::app::widgets::wrapper::{Hook, Wrapper};
+
+// Pixels scrolled by a wheel line, matches the iced scrollable widget
+const PIXELS_PER_LINE: f32 = 60.0;
+
+// Turn mouse wheel events over the content into messages, the content
+// does not receive them, so a scrollable content does not scroll
+pub struct OnWheel<'a, Message> {
+    // Called with the number of lines scrolled, positive when scrolling up
+    on_wheel: Box<dyn Fn(f32) -> Message + 'a>,
+}
+
+impl<'a, Message> OnWheel<'a, Message> {
+    pub fn new(
+        content: impl Into<Element<'a, Message>>,
+        on_wheel: impl Fn(f32) -> Message + 'a,
+    ) -> Wrapper<'a, Message, Self> {
+        Wrapper::new(
+            content,
+            OnWheel {
+                on_wheel: Box::new(on_wheel),
+            },
+        )
+    }
+}
+
+impl<Message> Hook<Message> for OnWheel<'_, Message> {
+    fn on_event(
+        &mut self,
+        event: &Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        if let Event::Mouse(mouse::Event::WheelScrolled { delta }) = event {
+            if cursor.is_over(layout.bounds()) {
+                let lines = match delta {
+                    ScrollDelta::Lines { y, .. } => *y,
+                    ScrollDelta::Pixels { y, .. } => y / PIXELS_PER_LINE,
+                };
+                shell.publish((self.on_wheel)(lines));
+                return event::Status::Captured;
+            }
+        }
+
+        event::Status::Ignored
+    }
+}
@@ -0,0 +1,151 @@
+use iced::{event, mouse, Element, Event, Length, Rectangle, Size, Vector};
+use iced_core::layout::{self, Layout};
+use iced_core::renderer;
+use iced_core::widget::{tree, Operation, Tree};
+use iced_core::{overlay, Clipboard, Shell, Widget};
+
+// What a `Wrapper` adds to its content, everything else is passed through
+pub trait Hook<Message> {
+    // Called before the content gets the event, which it does not when captured
+    fn on_event(
+        &mut self,
+        _event: &Event,
+        _layout: Layout<'_>,
+        _cursor: mouse::Cursor,
+        _shell: &mut Shell<'_, Message>,
+    ) -> event::Status {
+        event::Status::Ignored
+    }
+
+    // Called before the content is drawn
+    fn draw(&self, _renderer: &mut iced::Renderer, _layout: Layout<'_>) {}
+}
+
+// A widget taking the layout, state and children of its content
+pub struct Wrapper<'a, Message, H> {
+    content: Element<'a, Message>,
+    hook: H,
+}
+
+impl<'a, Message, H: Hook<Message>> Wrapper<'a, Message, H> {
+    pub fn new(content: impl Into<Element<'a, Message>>, hook: H) -> Self {
+        Self {
+            content: content.into(),
+            hook,
+        }
+    }
+}
+
+impl<Message, H: Hook<Message>> Widget<Message, iced::Theme, iced::Renderer>
+    for Wrapper<'_, Message, H>
+{
+    fn tag(&self) -> tree::Tag {
+        self.content.as_widget().tag()
+    }
+
+    fn state(&self) -> tree::State {
+        self.content.as_widget().state()
+    }
+
+    fn children(&self) -> Vec<Tree> {
+        self.content.as_widget().children()
+    }
+
+    fn diff(&self, tree: &mut Tree) {
+        self.content.as_widget().diff(tree);
+    }
+
+    fn size(&self) -> Size<Length> {
+        self.content.as_widget().size()
+    }
+
+    fn layout(
+        &self,
+        tree: &mut Tree,
+        renderer: &iced::Renderer,
+        limits: &layout::Limits,
+    ) -> layout::Node {
+        self.content.as_widget().layout(tree, renderer, limits)
+    }
+
+    fn operate(
+        &self,
+        tree: &mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        operation: &mut dyn Operation<Message>,
+    ) {
+        self.content
+            .as_widget()
+            .operate(tree, layout, renderer, operation);
+    }
+
+    fn on_event(
+        &mut self,
+        tree: &mut Tree,
+        event: Event,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        renderer: &iced::Renderer,
+        clipboard: &mut dyn Clipboard,
+        shell: &mut Shell<'_, Message>,
+        viewport: &Rectangle,
+    ) -> event::Status {
+        if let event::Status::Captured = self.hook.on_event(&event, layout, cursor, shell) {
+            return event::Status::Captured;
+        }
+
+        self.content.as_widget_mut().on_event(
+            tree, event, layout, cursor, renderer, clipboard, shell, viewport,
+        )
+    }
+
+    fn mouse_interaction(
+        &self,
+        tree: &Tree,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+        renderer: &iced::Renderer,
+    ) -> mouse::Interaction {
+        self.content
+            .as_widget()
+            .mouse_interaction(tree, layout, cursor, viewport, renderer)
+    }
+
+    fn draw(
+        &self,
+        tree: &Tree,
+        renderer: &mut iced::Renderer,
+        theme: &iced::Theme,
+        style: &renderer::Style,
+        layout: Layout<'_>,
+        cursor: mouse::Cursor,
+        viewport: &Rectangle,
+    ) {
+        self.hook.draw(renderer, layout);
+        self.content
+            .as_widget()
+            .draw(tree, renderer, theme, style, layout, cursor, viewport);
+    }
+
+    fn overlay<'b>(
+        &'b mut self,
+        tree: &'b mut Tree,
+        layout: Layout<'_>,
+        renderer: &iced::Renderer,
+        translation: Vector,
+    ) -> Option<overlay::Element<'b, Message, iced::Theme, iced::Renderer>> {
+        self.content
+            .as_widget_mut()
+            .overlay(tree, layout, renderer, translation)
+    }
+}
+
+impl<'a, Message: 'a, H: Hook<Message> + 'a> From<Wrapper<'a, Message, H>>
+    for Element<'a, Message>
+{
+    fn from(wrapper: Wrapper<'a, Message, H>) -> Self {
+        Element::new(wrapper)
+    }
+}
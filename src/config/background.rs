@@ -0,0 +1,132 @@
+use crate::config::color::OnagreColor;
+use crate::config::error::ConfigError;
+use iced::gradient::Linear;
+use iced::{Background, Degrees};
+
+// Either a plain color or a linear gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OnagreBackground {
+    Color(OnagreColor),
+    Gradient(OnagreGradient),
+}
+
+impl Eq for OnagreBackground {}
+
+impl OnagreBackground {
+    pub(crate) const DEFAULT_BACKGROUND: OnagreBackground =
+        OnagreBackground::Color(OnagreColor::DEFAULT_BACKGROUND);
+
+    pub(crate) const DEFAULT_SCROLL: OnagreBackground =
+        OnagreBackground::Color(OnagreColor::DEFAULT_SCROLL);
+
+    pub(crate) const TRANSPARENT: OnagreBackground =
+        OnagreBackground::Color(OnagreColor::TRANSPARENT);
+
+    pub(crate) const WHITE: OnagreBackground = OnagreBackground::Color(OnagreColor::WHITE);
+
+    // The background children inherit from their parent.
+    // Gradients are not repeated in every child, they become transparent instead
+    // so the parent gradient stay visible.
+    pub(crate) fn inherited(self) -> OnagreBackground {
+        match self {
+            OnagreBackground::Color(_) => self,
+            OnagreBackground::Gradient(_) => OnagreBackground::TRANSPARENT,
+        }
+    }
+
+    // Blend two backgrounds, weight is the proportion of `self` in the result.
+    // Gradients can't be interpolated, the closest background wins.
+    pub(crate) fn mix(self, other: OnagreBackground, weight: f32) -> OnagreBackground {
+        match (self, other) {
+            (OnagreBackground::Color(color), OnagreBackground::Color(other)) => {
+                OnagreBackground::Color(color.mix(other, weight))
+            }
+            _ if weight >= 0.5 => self,
+            _ => other,
+        }
+    }
+}
+
+impl From<OnagreColor> for OnagreBackground {
+    fn from(color: OnagreColor) -> Self {
+        OnagreBackground::Color(color)
+    }
+}
+
+impl From<OnagreBackground> for Background {
+    fn from(background: OnagreBackground) -> Self {
+        match background {
+            OnagreBackground::Color(color) => Background::Color(color.into()),
+            OnagreBackground::Gradient(gradient) => Background::Gradient(gradient.linear.into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OnagreGradient {
+    linear: Linear,
+}
+
+impl OnagreGradient {
+    // Iced gradients support at most 8 color stops
+    const MAX_STOPS: usize = 8;
+
+    // Angle follows the css convention: 0deg points to the top, 90deg to the right.
+    // Stops without offset are evenly distributed
+    pub(crate) fn new(
+        angle: f32,
+        stops: Vec<(OnagreColor, Option<f32>)>,
+    ) -> Result<Self, ConfigError> {
+        if stops.len() > Self::MAX_STOPS {
+            return Err(ConfigError::TooManyColorStops(stops.len()));
+        }
+
+        let last = (stops.len() - 1).max(1) as f32;
+        let linear = stops.into_iter().enumerate().fold(
+            Linear::new(Degrees(angle)),
+            |linear, (idx, (color, offset))| {
+                let offset = offset.unwrap_or(idx as f32 / last);
+                linear.add_stop(offset.clamp(0.0, 1.0), color.into())
+            },
+        );
+
+        Ok(OnagreGradient { linear })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::background::OnagreGradient;
+    use crate::config::color::OnagreColor;
+    use iced::gradient::ColorStop;
+
+    #[test]
+    fn should_distribute_color_stops() {
+        let gradient = OnagreGradient::new(
+            90.0,
+            vec![
+                (OnagreColor::WHITE, None),
+                (OnagreColor::RED, None),
+                (OnagreColor::TRANSPARENT, None),
+            ],
+        )
+        .unwrap();
+
+        let offsets: Vec<f32> = gradient
+            .linear
+            .stops
+            .iter()
+            .flatten()
+            .map(|ColorStop { offset, .. }| *offset)
+            .collect();
+
+        assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn should_reject_too_many_stops() {
+        let gradient = OnagreGradient::new(0.0, vec![(OnagreColor::WHITE, None); 9]);
+
+        assert!(gradient.is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::config::error::ConfigError;
+use crate::config::named_colors::NAMED_COLORS;
 use iced::Color;
 use std::{fmt::Display, num::ParseIntError};
 
@@ -109,6 +110,135 @@ impl OnagreColor {
     fn f32_from_str_hex(hex_color: &str) -> Result<f32, ParseIntError> {
         u32::from_str_radix(hex_color, 16).map(|value| value as f32 / 255.0)
     }
+
+    // Build a color from normalized (0.0 to 1.0) channels
+    pub(crate) fn from_rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        OnagreColor {
+            color: Color {
+                r: r.clamp(0.0, 1.0),
+                g: g.clamp(0.0, 1.0),
+                b: b.clamp(0.0, 1.0),
+                a: a.clamp(0.0, 1.0),
+            },
+        }
+    }
+
+    // Hue is expressed in degrees, saturation, lightness and alpha are normalized
+    pub(crate) fn from_hsla(h: f32, s: f32, l: f32, a: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 360.0;
+        let s = s.clamp(0.0, 1.0);
+        let l = l.clamp(0.0, 1.0);
+
+        if s == 0.0 {
+            return OnagreColor::from_rgba(l, l, l, a);
+        }
+
+        let q = if l < 0.5 {
+            l * (1.0 + s)
+        } else {
+            l + s - l * s
+        };
+        let p = 2.0 * l - q;
+
+        // Round to 8 bits channels to get rid of floating point noise
+        let channel = |t: f32| (hue_to_rgb(p, q, t) * 255.0).round() / 255.0;
+
+        OnagreColor::from_rgba(
+            channel(h + 1.0 / 3.0),
+            channel(h),
+            channel(h - 1.0 / 3.0),
+            a,
+        )
+    }
+
+    pub(crate) fn from_name(name: &str) -> Result<Self, ConfigError> {
+        let name = name.to_lowercase();
+        if name == "transparent" {
+            return Ok(OnagreColor::TRANSPARENT);
+        }
+
+        NAMED_COLORS
+            .iter()
+            .find(|(color_name, _)| *color_name == name)
+            .map(|(_, rgb)| {
+                OnagreColor::from_rgba(
+                    ((rgb >> 16) & 0xff) as f32 / 255.0,
+                    ((rgb >> 8) & 0xff) as f32 / 255.0,
+                    (rgb & 0xff) as f32 / 255.0,
+                    1.0,
+                )
+            })
+            .ok_or(ConfigError::ParseColor(name))
+    }
+
+    // Returns hue in degrees, saturation, lightness and alpha
+    fn to_hsla(self) -> (f32, f32, f32, f32) {
+        let Color { r, g, b, a } = self.color;
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let l = (max + min) / 2.0;
+
+        if max == min {
+            return (0.0, 0.0, l, a);
+        }
+
+        let d = max - min;
+        let s = if l > 0.5 {
+            d / (2.0 - max - min)
+        } else {
+            d / (max + min)
+        };
+
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+
+        (h * 60.0, s, l, a)
+    }
+
+    // Increase the lightness by the given amount (0.0 to 1.0)
+    pub(crate) fn lighten(self, amount: f32) -> Self {
+        let (h, s, l, a) = self.to_hsla();
+        OnagreColor::from_hsla(h, s, l + amount, a)
+    }
+
+    // Decrease the lightness by the given amount (0.0 to 1.0)
+    pub(crate) fn darken(self, amount: f32) -> Self {
+        self.lighten(-amount)
+    }
+
+    // Mix two colors, weight is the proportion of `self` in the result (0.0 to 1.0)
+    pub(crate) fn mix(self, other: OnagreColor, weight: f32) -> Self {
+        let weight = weight.clamp(0.0, 1.0);
+        let mix = |a: f32, b: f32| a * weight + b * (1.0 - weight);
+        OnagreColor::from_rgba(
+            mix(self.color.r, other.color.r),
+            mix(self.color.g, other.color.g),
+            mix(self.color.b, other.color.b),
+            mix(self.color.a, other.color.a),
+        )
+    }
+
+    pub(crate) fn with_alpha(self, alpha: f32) -> Self {
+        OnagreColor::from_rgba(self.color.r, self.color.g, self.color.b, alpha)
+    }
+}
+
+fn hue_to_rgb(p: f32, q: f32, t: f32) -> f32 {
+    let t = t.rem_euclid(1.0);
+    if t < 1.0 / 6.0 {
+        p + (q - p) * 6.0 * t
+    } else if t < 1.0 / 2.0 {
+        q
+    } else if t < 2.0 / 3.0 {
+        p + (q - p) * (2.0 / 3.0 - t) * 6.0
+    } else {
+        p
+    }
 }
 
 impl Display for OnagreColor {
@@ -208,6 +338,48 @@ mod test {
         );
     }
 
+    #[test]
+    fn should_get_color_from_hsl() {
+        let color = OnagreColor::from_hsla(0.0, 1.0, 0.5, 1.0);
+
+        assert_eq!(OnagreColor::RED, color);
+    }
+
+    #[test]
+    fn should_get_named_color() {
+        let color = OnagreColor::from_name("RebeccaPurple");
+
+        assert_eq!(OnagreColor::from("#663399").unwrap(), color.unwrap());
+    }
+
+    #[test]
+    fn should_lighten_and_darken_color() {
+        let color = OnagreColor::from("#808080").unwrap();
+
+        assert_eq!(color.lighten(1.0), OnagreColor::WHITE);
+        assert_eq!(color.darken(1.0), OnagreColor::from("#000000").unwrap());
+    }
+
+    #[test]
+    fn should_mix_colors() {
+        let black = OnagreColor::from("#000000").unwrap();
+        let white = OnagreColor::WHITE;
+
+        let color = black.mix(white, 0.5);
+
+        assert_eq!(
+            OnagreColor {
+                color: Color {
+                    r: 0.5,
+                    g: 0.5,
+                    b: 0.5,
+                    a: 1.0,
+                }
+            },
+            color
+        );
+    }
+
     #[test]
     fn parse_error() {
         let hex_color = "#II";
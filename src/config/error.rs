@@ -1,6 +1,7 @@
 use super::Rule;
 use pest::error::Error as PestError;
 use std::num::{ParseFloatError, ParseIntError};
+use std::path::PathBuf;
 use thiserror::Error;
 use tokio::io;
 
@@ -14,6 +15,14 @@ pub enum ConfigError {
     ParseInt(#[from] ParseIntError),
     #[error("Failed to parse number")]
     ParseFloat(#[from] ParseFloatError),
-    #[error("Failed to parse '{0}' as hex color")]
+    #[error("Failed to parse '{0}' as color")]
     ParseColor(String),
+    #[error("Expected a percentage or a value between 0 and 1, got '{0}'")]
+    AmountOutOfRange(String),
+    #[error("Gradients support at most 8 color stops, got {0}")]
+    TooManyColorStops(usize),
+    #[error("Imported theme {0:?} not found")]
+    ImportNotFound(PathBuf),
+    #[error("Import cycle detected, {0:?} is already being imported")]
+    ImportCycle(PathBuf),
 }
@@ -1,9 +1,16 @@
+use crate::app::style::preview::PreviewPosition;
+use crate::app::style::scrollable::RowsLayout;
+use crate::app::style::{AppLayout, ClickActivation};
+use crate::config::background::{OnagreBackground, OnagreGradient};
 use crate::config::color::OnagreColor;
 use crate::config::error::ConfigError;
+use crate::config::radius::OnagreRadius;
+use crate::config::shadow::OnagreShadow;
 use crate::config::Rule;
 use iced::alignment::{Horizontal, Vertical};
 use iced::Length;
 use pest::iterators::Pair;
+use std::time::Duration;
 
 // Helper functions to get values from the css like theme config file.
 // We don't mind unwrapping here since pest
@@ -38,6 +45,52 @@ pub fn unwrap_attr_f32(pair: Pair<'_, Rule>) -> Result<f32, ConfigError> {
         .map_err(ConfigError::from)
 }
 
+pub fn unwrap_radius(pair: Pair<'_, Rule>) -> Result<OnagreRadius, ConfigError> {
+    let values = pair
+        .into_inner()
+        .map(|value| value.into_inner().as_str().parse::<f32>())
+        .collect::<Result<Vec<f32>, _>>()?;
+
+    Ok(OnagreRadius::from_css(&values))
+}
+
+pub fn unwrap_shadow(pair: Pair<'_, Rule>) -> Result<OnagreShadow, ConfigError> {
+    let shadow = pair.into_inner().last().unwrap();
+    if shadow.as_rule() == Rule::shadow_none {
+        return Ok(OnagreShadow::NONE);
+    }
+
+    let mut offsets = vec![];
+    let mut color = OnagreColor::TRANSPARENT;
+    for pair in shadow.into_inner() {
+        match pair.as_rule() {
+            Rule::offset_value => {
+                offsets.push(pair.as_str().trim_end_matches("px").parse::<f32>()?)
+            }
+            Rule::color_value => color = color_from_pair(pair)?,
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(OnagreShadow {
+        color,
+        offset_x: offsets[0],
+        offset_y: offsets[1],
+        blur_radius: offsets.get(2).copied().unwrap_or(0.0),
+    })
+}
+
+pub fn unwrap_duration(pair: Pair<'_, Rule>) -> Result<Duration, ConfigError> {
+    let mut duration = pair.into_inner().last().unwrap().into_inner();
+    let value = duration.next().unwrap().as_str().parse::<f32>()?;
+    let millis = match duration.next().unwrap().as_rule() {
+        Rule::seconds => value * 1000.0,
+        _ => value,
+    };
+
+    Ok(Duration::from_millis(millis as u64))
+}
+
 pub fn unwrap_attr_str(pair: Pair<'_, Rule>) -> &str {
     pair.into_inner().last().unwrap().into_inner().as_str()
 }
@@ -48,10 +101,148 @@ pub fn unwrap_attr_bool(pair: Pair<'_, Rule>) -> bool {
     value == "true"
 }
 
-pub fn unwrap_hex_color(pair: Pair<'_, Rule>) -> Result<OnagreColor, ConfigError> {
-    let color = pair.into_inner().last().unwrap().as_str();
+pub fn unwrap_color(pair: Pair<'_, Rule>) -> Result<OnagreColor, ConfigError> {
+    let color = pair.into_inner().last().unwrap();
+
+    color_from_pair(color)
+}
+
+pub fn unwrap_background(pair: Pair<'_, Rule>) -> Result<OnagreBackground, ConfigError> {
+    let background = pair.into_inner().last().unwrap();
+    let background = background.into_inner().next().unwrap();
+
+    match background.as_rule() {
+        Rule::color_value => color_from_pair(background).map(OnagreBackground::from),
+        Rule::linear_gradient => {
+            let mut angle = 180.0;
+            let mut stops = vec![];
+            for pair in background.into_inner() {
+                match pair.as_rule() {
+                    Rule::gradient_direction => angle = unwrap_gradient_angle(pair)?,
+                    Rule::color_stop => {
+                        let mut stop = pair.into_inner();
+                        let color = color_from_pair(stop.next().unwrap())?;
+                        let offset = stop
+                            .next()
+                            .map(|offset| offset.into_inner().as_str().parse::<f32>())
+                            .transpose()?
+                            .map(|offset| offset / 100.0);
+                        stops.push((color, offset));
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            OnagreGradient::new(angle, stops).map(OnagreBackground::Gradient)
+        }
+        _ => unreachable!(),
+    }
+}
+
+// Css like gradient angle in degrees, either explicit or from a 'to {side}' direction
+fn unwrap_gradient_angle(pair: Pair<'_, Rule>) -> Result<f32, ConfigError> {
+    let direction = pair.into_inner().next().unwrap();
+    match direction.as_rule() {
+        Rule::angle => Ok(direction.into_inner().as_str().parse::<f32>()?),
+        Rule::side_or_corner => {
+            let sides: Vec<Rule> = direction.into_inner().map(|side| side.as_rule()).collect();
+            Ok(match sides.as_slice() {
+                [Rule::top] => 0.0,
+                [Rule::top, Rule::right] => 45.0,
+                [Rule::right] => 90.0,
+                [Rule::bottom, Rule::right] => 135.0,
+                [Rule::bottom, Rule::left] => 225.0,
+                [Rule::left] => 270.0,
+                [Rule::top, Rule::left] => 315.0,
+                _ => 180.0,
+            })
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub fn color_from_pair(pair: Pair<'_, Rule>) -> Result<OnagreColor, ConfigError> {
+    let pair = pair.into_inner().next().unwrap();
+
+    match pair.as_rule() {
+        Rule::hex_color => OnagreColor::from(pair.as_str()),
+        Rule::named_color => OnagreColor::from_name(pair.as_str()),
+        Rule::rgb_color => {
+            let mut channels = pair.into_inner();
+            let r = unwrap_rgb_channel(channels.next().unwrap())?;
+            let g = unwrap_rgb_channel(channels.next().unwrap())?;
+            let b = unwrap_rgb_channel(channels.next().unwrap())?;
+            let a = channels.next().map(unwrap_channel).transpose()?;
+            Ok(OnagreColor::from_rgba(r, g, b, a.unwrap_or(1.0)))
+        }
+        Rule::hsl_color => {
+            let mut channels = pair.into_inner();
+            let h = channels
+                .next()
+                .unwrap()
+                .into_inner()
+                .as_str()
+                .parse::<f32>()?;
+            let s = unwrap_percent_channel(channels.next().unwrap())?;
+            let l = unwrap_percent_channel(channels.next().unwrap())?;
+            let a = channels.next().map(unwrap_channel).transpose()?;
+            Ok(OnagreColor::from_hsla(h, s, l, a.unwrap_or(1.0)))
+        }
+        Rule::color_function => {
+            let function = pair.into_inner().next().unwrap();
+            let rule = function.as_rule();
+            let mut args = function.into_inner();
+            let color = color_from_pair(args.next().unwrap())?;
+            match rule {
+                Rule::lighten => Ok(color.lighten(unwrap_channel(args.next().unwrap())?)),
+                Rule::darken => Ok(color.darken(unwrap_channel(args.next().unwrap())?)),
+                Rule::alpha => Ok(color.with_alpha(unwrap_channel(args.next().unwrap())?)),
+                Rule::mix => {
+                    let other = color_from_pair(args.next().unwrap())?;
+                    let weight = args.next().map(unwrap_channel).transpose()?;
+                    Ok(color.mix(other, weight.unwrap_or(0.5)))
+                }
+                _ => unreachable!(),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+// Percentages are normalized, plain values are expected to be in the 0.0 to 1.0 range
+fn unwrap_channel(pair: Pair<'_, Rule>) -> Result<f32, ConfigError> {
+    let value = pair.into_inner().next().unwrap();
+    let amount = match value.as_rule() {
+        Rule::percentage => value.clone().into_inner().as_str().parse::<f32>()? / 100.0,
+        Rule::decimal => value.as_str().parse::<f32>()?,
+        _ => unreachable!(),
+    };
 
-    OnagreColor::from(color)
+    if (0.0..=1.0).contains(&amount) {
+        Ok(amount)
+    } else {
+        Err(ConfigError::AmountOutOfRange(value.as_str().to_string()))
+    }
+}
+
+// Rgb channels are either percentages or values in the 0 to 255 range
+fn unwrap_rgb_channel(pair: Pair<'_, Rule>) -> Result<f32, ConfigError> {
+    let value = pair.into_inner().next().unwrap();
+    match value.as_rule() {
+        Rule::percentage => Ok(value.into_inner().as_str().parse::<f32>()? / 100.0),
+        Rule::decimal => Ok(value.as_str().parse::<f32>()? / 255.0),
+        _ => unreachable!(),
+    }
+}
+
+// Saturation and lightness are always percentages, even if the '%' sign is omitted
+fn unwrap_percent_channel(pair: Pair<'_, Rule>) -> Result<f32, ConfigError> {
+    let value = pair.into_inner().next().unwrap();
+    match value.as_rule() {
+        Rule::percentage => Ok(value.into_inner().as_str().parse::<f32>()? / 100.0),
+        Rule::decimal => Ok(value.as_str().parse::<f32>()? / 100.0),
+        _ => unreachable!(),
+    }
 }
 
 pub fn unwrap_x(pair: Pair<'_, Rule>) -> Result<Horizontal, ConfigError> {
@@ -78,6 +269,38 @@ pub fn unwrap_y(pair: Pair<'_, Rule>) -> Result<Vertical, ConfigError> {
     }
 }
 
+pub fn unwrap_layout(pair: Pair<'_, Rule>) -> RowsLayout {
+    let layout = pair.into_inner().last().unwrap();
+    match layout.into_inner().next().unwrap().as_rule() {
+        Rule::grid => RowsLayout::Grid,
+        _ => RowsLayout::List,
+    }
+}
+
+pub fn unwrap_position(pair: Pair<'_, Rule>) -> PreviewPosition {
+    let position = pair.into_inner().last().unwrap();
+    match position.into_inner().next().unwrap().as_rule() {
+        Rule::bottom => PreviewPosition::Bottom,
+        _ => PreviewPosition::Right,
+    }
+}
+
+pub fn unwrap_click_activation(pair: Pair<'_, Rule>) -> ClickActivation {
+    let activation = pair.into_inner().last().unwrap();
+    match activation.into_inner().next().unwrap().as_rule() {
+        Rule::double_click => ClickActivation::Double,
+        _ => ClickActivation::Single,
+    }
+}
+
+pub fn unwrap_app_layout(pair: Pair<'_, Rule>) -> AppLayout {
+    let layout = pair.into_inner().last().unwrap();
+    match layout.into_inner().next().unwrap().as_rule() {
+        Rule::bar_layout => AppLayout::Bar,
+        _ => AppLayout::Vertical,
+    }
+}
+
 pub fn unwrap_length(pair: Pair<'_, Rule>) -> Result<Length, ConfigError> {
     let lenght = pair.into_inner().last().unwrap();
     let mut lenght = lenght.into_inner();
@@ -101,16 +324,22 @@ pub fn unwrap_length(pair: Pair<'_, Rule>) -> Result<Length, ConfigError> {
 
 #[cfg(test)]
 mod test {
+    use crate::app::style::ClickActivation;
+    use crate::config::background::{OnagreBackground, OnagreGradient};
     use crate::config::color::OnagreColor;
     use crate::config::helpers::{
-        unwrap_attr_bool, unwrap_attr_f32, unwrap_attr_str, unwrap_attr_u16, unwrap_hex_color,
-        unwrap_length, unwrap_x, unwrap_y,
+        unwrap_attr_bool, unwrap_attr_f32, unwrap_attr_str, unwrap_attr_u16, unwrap_background,
+        unwrap_click_activation, unwrap_color, unwrap_duration, unwrap_length, unwrap_radius,
+        unwrap_shadow, unwrap_x, unwrap_y,
     };
+    use crate::config::radius::OnagreRadius;
+    use crate::config::shadow::OnagreShadow;
     use crate::config::{Rule, ThemeParser};
     use iced::alignment::{Horizontal, Vertical};
     use iced::Length;
     use pest::Parser;
     use speculoos::prelude::*;
+    use std::time::Duration;
 
     #[test]
     fn should_parse_align_x() {
@@ -187,6 +416,103 @@ mod test {
             .is_equal_to(1.0);
     }
 
+    #[test]
+    fn should_parse_per_corner_border_radius() {
+        let pair = ThemeParser::parse(Rule::border_radius, "border-radius: 8px 8px 0px 0px;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let border_radius = unwrap_radius(pair);
+
+        asserting!("Should parse 'border-radius' corners")
+            .that(&border_radius)
+            .is_ok()
+            .is_equal_to(OnagreRadius {
+                top_left: 8.0,
+                top_right: 8.0,
+                bottom_right: 0.0,
+                bottom_left: 0.0,
+            });
+    }
+
+    #[test]
+    fn should_expand_two_values_border_radius() {
+        let pair = ThemeParser::parse(Rule::border_radius, "border-radius: 4 2;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let border_radius = unwrap_radius(pair);
+
+        asserting!("Should expand 'border-radius' values")
+            .that(&border_radius)
+            .is_ok()
+            .is_equal_to(OnagreRadius {
+                top_left: 4.0,
+                top_right: 2.0,
+                bottom_right: 4.0,
+                bottom_left: 2.0,
+            });
+    }
+
+    #[test]
+    fn should_parse_box_shadow() {
+        let pair = ThemeParser::parse(
+            Rule::box_shadow,
+            "box-shadow: 0 -4px 12px rgba(0, 0, 0, 0.5);",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+
+        let shadow = unwrap_shadow(pair);
+
+        asserting!("Should parse 'box-shadow' attribute")
+            .that(&shadow)
+            .is_ok()
+            .is_equal_to(OnagreShadow {
+                color: OnagreColor::from_rgba(0.0, 0.0, 0.0, 0.5),
+                offset_x: 0.0,
+                offset_y: -4.0,
+                blur_radius: 12.0,
+            });
+    }
+
+    #[test]
+    fn should_parse_box_shadow_none() {
+        let pair = ThemeParser::parse(Rule::box_shadow, "box-shadow: none;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let shadow = unwrap_shadow(pair);
+
+        asserting!("Should parse 'box-shadow: none'")
+            .that(&shadow)
+            .is_ok()
+            .is_equal_to(OnagreShadow::NONE);
+    }
+
+    #[test]
+    fn should_parse_transition_duration() {
+        let seconds = ThemeParser::parse(Rule::transition_duration, "transition-duration: 0.2s;")
+            .unwrap()
+            .next()
+            .unwrap();
+        let millis = ThemeParser::parse(Rule::transition_duration, "transition-duration: 150ms;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        assert_that!(unwrap_duration(seconds))
+            .is_ok()
+            .is_equal_to(Duration::from_millis(200));
+        assert_that!(unwrap_duration(millis))
+            .is_ok()
+            .is_equal_to(Duration::from_millis(150));
+    }
+
     #[test]
     fn should_parse_border_color() {
         let pair = ThemeParser::parse(Rule::border_color, "border-color: #ffffff;")
@@ -194,7 +520,7 @@ mod test {
             .next()
             .unwrap();
 
-        let border_color = unwrap_hex_color(pair);
+        let border_color = unwrap_color(pair);
 
         asserting!("Should parse 'border-color' attribute")
             .that(&border_color)
@@ -209,7 +535,7 @@ mod test {
             .next()
             .unwrap();
 
-        let color = unwrap_hex_color(pair);
+        let color = unwrap_color(pair);
 
         asserting!("Should parse 'color' attribute")
             .that(&color)
@@ -224,12 +550,122 @@ mod test {
             .next()
             .unwrap();
 
-        let background = unwrap_hex_color(pair);
+        let background = unwrap_background(pair);
 
         asserting!("Should parse 'background' attribute")
             .that(&background)
             .is_ok()
-            .is_equal_to(OnagreColor::from("#ffffff").unwrap());
+            .is_equal_to(OnagreBackground::from(
+                OnagreColor::from("#ffffff").unwrap(),
+            ));
+    }
+
+    #[test]
+    fn should_parse_rgba_color() {
+        let pair = ThemeParser::parse(Rule::background, "background: rgba(255, 0, 255, 50%);")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let background = unwrap_background(pair);
+
+        asserting!("Should parse 'rgba' color")
+            .that(&background)
+            .is_ok()
+            .is_equal_to(OnagreBackground::from(OnagreColor::from_rgba(
+                1.0, 0.0, 1.0, 0.5,
+            )));
+    }
+
+    #[test]
+    fn should_parse_hsl_color() {
+        let pair = ThemeParser::parse(Rule::color, "color: hsl(0deg, 100%, 50%);")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let color = unwrap_color(pair);
+
+        asserting!("Should parse 'hsl' color")
+            .that(&color)
+            .is_ok()
+            .is_equal_to(OnagreColor::RED);
+    }
+
+    #[test]
+    fn should_parse_named_color() {
+        let pair = ThemeParser::parse(Rule::border_color, "border-color: transparent;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let border_color = unwrap_color(pair);
+
+        asserting!("Should parse named color")
+            .that(&border_color)
+            .is_ok()
+            .is_equal_to(OnagreColor::TRANSPARENT);
+    }
+
+    #[test]
+    fn should_parse_nested_color_functions() {
+        let pair = ThemeParser::parse(
+            Rule::background,
+            "background: alpha(mix(black, white, 50%), 0.5);",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+
+        let background = unwrap_background(pair);
+
+        asserting!("Should parse color functions")
+            .that(&background)
+            .is_ok()
+            .is_equal_to(OnagreBackground::from(OnagreColor::from_rgba(
+                0.5, 0.5, 0.5, 0.5,
+            )));
+    }
+
+    #[test]
+    fn should_fail_on_unknown_named_color() {
+        let pair = ThemeParser::parse(Rule::color, "color: notacolor;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let color = unwrap_color(pair);
+
+        asserting!("Should not parse unknown color")
+            .that(&color)
+            .is_err();
+    }
+
+    #[test]
+    fn should_parse_linear_gradient() {
+        let pair = ThemeParser::parse(
+            Rule::background,
+            "background: linear-gradient(to right, #ffffff, rgba(255, 0, 0, 0.5) 80%);",
+        )
+        .unwrap()
+        .next()
+        .unwrap();
+
+        let background = unwrap_background(pair);
+
+        asserting!("Should parse linear gradient")
+            .that(&background)
+            .is_ok()
+            .is_equal_to(OnagreBackground::Gradient(
+                OnagreGradient::new(
+                    90.0,
+                    vec![
+                        (OnagreColor::WHITE, None),
+                        (OnagreColor::from_rgba(1.0, 0.0, 0.0, 0.5), Some(0.8)),
+                    ],
+                )
+                .unwrap(),
+            ));
     }
 
     #[test]
@@ -513,4 +949,16 @@ mod test {
             .that(&exit_unfocused)
             .is_true();
     }
+
+    #[test]
+    fn should_parse_click_activation() {
+        let pair = ThemeParser::parse(Rule::click_activation, "--click-activation: double;")
+            .unwrap()
+            .next()
+            .unwrap();
+
+        let activation = unwrap_click_activation(pair);
+
+        assert_that!(activation).is_equal_to(ClickActivation::Double);
+    }
 }
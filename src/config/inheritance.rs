@@ -1,8 +1,9 @@
 use crate::app::style::app::AppContainerStyles;
+use crate::app::style::preview::PreviewStyles;
 use crate::app::style::rows::RowStyles;
 use crate::app::style::scrollable::RowContainerStyle;
 use crate::app::style::search::SearchContainerStyles;
-use crate::Theme;
+use crate::app::style::Theme;
 
 // Propagate style from parent to children
 // Unless implemented this should never be called
@@ -26,7 +27,7 @@ pub trait Inherit {
 
 impl Inherit for Theme {
     fn propagate_background(&mut self) {
-        self.app_container.background = self.background;
+        self.app_container.background = self.background.inherited();
         self.app_container.propagate_background();
     }
 
@@ -53,13 +54,22 @@ impl Inherit for Theme {
 
 impl Inherit for AppContainerStyles {
     fn propagate_background(&mut self) {
-        self.rows.background = self.background;
+        self.rows.background = self.background.inherited();
         self.rows.propagate_background();
 
-        self.search.background = self.background;
+        self.search.background = self.background.inherited();
         self.search.propagate_background();
 
-        self.scrollable.background = self.background;
+        self.scrollable.background = self.background.inherited();
+
+        if let Some(preview) = &mut self.preview {
+            preview.background = self.background.inherited();
+            preview.propagate_background();
+        }
+
+        if let Some(status) = &mut self.status {
+            status.background = self.background.inherited();
+        }
     }
 
     fn propagate_color(&mut self) {
@@ -68,15 +78,34 @@ impl Inherit for AppContainerStyles {
         self.search.color = self.color;
         self.search.propagate_color();
         self.scrollable.scroller_color = self.color;
+
+        if let Some(preview) = &mut self.preview {
+            preview.color = self.color;
+            preview.propagate_color();
+        }
+
+        if let Some(status) = &mut self.status {
+            status.color = self.color;
+        }
+    }
+}
+
+impl Inherit for PreviewStyles {
+    fn propagate_background(&mut self) {
+        self.title.background = self.background.inherited();
+    }
+
+    fn propagate_color(&mut self) {
+        self.title.color = self.color;
     }
 }
 
 impl Inherit for RowContainerStyle {
     fn propagate_background(&mut self) {
-        self.row.background = self.background;
+        self.row.background = self.background.inherited();
         self.row.propagate_background();
 
-        self.row_selected.background = self.background;
+        self.row_selected.background = self.background.inherited();
         self.row_selected.propagate_background();
     }
 
@@ -91,10 +120,10 @@ impl Inherit for RowContainerStyle {
 
 impl Inherit for RowStyles {
     fn propagate_background(&mut self) {
-        self.title.background = self.background;
-        self.description.background = self.background;
-        self.icon.background = self.background;
-        self.category_icon.background = self.background;
+        self.title.background = self.background.inherited();
+        self.description.background = self.background.inherited();
+        self.icon.background = self.background.inherited();
+        self.category_icon.background = self.background.inherited();
     }
 
     fn propagate_color(&mut self) {
@@ -107,7 +136,7 @@ impl Inherit for RowStyles {
 
 impl Inherit for SearchContainerStyles {
     fn propagate_background(&mut self) {
-        let background = self.background;
+        let background = self.background.inherited();
         self.input.background = background;
         if let Some(hint) = &mut self.plugin_hint {
             hint.background = background;
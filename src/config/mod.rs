@@ -1,18 +1,24 @@
+pub mod background;
 pub mod color;
 mod error;
 mod helpers;
 mod inheritance;
+mod named_colors;
 pub mod padding;
+pub mod radius;
+pub mod scripts;
+pub mod shadow;
+pub mod themes;
 
 use error::ConfigError;
 use inheritance::Inherit;
-use pest::iterators::Pair;
+use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser;
-use std::convert::TryFrom;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::app::style::app::AppContainerStyles;
+use crate::app::style::app::{AppContainerStyles, ModeRowsOverride};
+use crate::app::style::preview::PreviewStyles;
 use crate::app::style::rows::generic::GenericContainerStyle;
 use crate::app::style::rows::icon::IconStyle;
 use crate::app::style::rows::RowStyles;
@@ -20,84 +26,157 @@ use crate::app::style::scrollable::scroller::ScrollerStyles;
 use crate::app::style::scrollable::RowContainerStyle;
 use crate::app::style::search::input::SearchInputStyles;
 use crate::app::style::search::SearchContainerStyles;
+use crate::app::style::status::StatusStyles;
 use crate::app::style::Theme;
+use crate::freedesktop::settings::ColorScheme;
 use padding::OnagrePadding;
 
 #[derive(Parser)]
 #[grammar = "config/grammar.pest"]
 struct ThemeParser;
 
-pub fn parse_file<P: AsRef<Path>>(path: P) -> Result<Theme, ConfigError> {
-    let content = std::fs::read_to_string(path)?;
+pub fn parse_file_for_scheme<P: AsRef<Path>>(
+    path: P,
+    color_scheme: ColorScheme,
+) -> Result<Theme, ConfigError> {
+    parse_file_with_scheme(path, &|| color_scheme)
+}
+
+// The color scheme is only read when the theme has `prefers-color-scheme` rules
+pub fn parse_file_with_scheme<P: AsRef<Path>>(
+    path: P,
+    color_scheme: &dyn Fn() -> ColorScheme,
+) -> Result<Theme, ConfigError> {
+    let mut theme = Theme::base();
+    let mut import_stack = vec![];
+    apply_file(&mut theme, path.as_ref(), color_scheme, &mut import_stack)?;
+    theme.app_container.apply_mode_rows()?;
+    Ok(theme)
+}
 
+// Apply a stylesheet and all its imports on top of the given theme.
+// Imports are applied depth first, before the importing file own rules, so they
+// act as a base the importing file can override.
+fn apply_file(
+    theme: &mut Theme,
+    path: &Path,
+    color_scheme: &dyn Fn() -> ColorScheme,
+    import_stack: &mut Vec<PathBuf>,
+) -> Result<(), ConfigError> {
+    let path = path.canonicalize()?;
+    if import_stack.contains(&path) {
+        return Err(ConfigError::ImportCycle(path));
+    }
+
+    let content = std::fs::read_to_string(&path)?;
     let pairs = ThemeParser::parse(Rule::stylesheet, &content)
-        .map_err(Box::new)?
+        .map_err(|err| Box::new(err.with_path(&path.to_string_lossy())))?
         .next()
         .unwrap();
 
-    if let Some(pair) = pairs.into_inner().next() {
+    import_stack.push(path.clone());
+    for pair in pairs.into_inner() {
         match pair.as_rule() {
-            Rule::onagre_style => {
-                return Theme::try_from(pair);
+            Rule::import => {
+                let import = helpers::unwrap_attr_str(pair);
+                let import = path
+                    .parent()
+                    .expect("Theme file should have a parent directory")
+                    .join(import);
+
+                if !import.exists() {
+                    return Err(ConfigError::ImportNotFound(import));
+                }
+
+                apply_file(theme, &import, color_scheme, import_stack)?;
+            }
+            Rule::onagre_style => theme.apply(pair)?,
+            Rule::media_query => {
+                let mut inner = pair.into_inner();
+                let query = inner.next().unwrap();
+                if color_scheme().matches(query.as_str()) {
+                    theme.apply(inner.next().unwrap())?;
+                }
             }
+            Rule::EOI => break,
             _ => unreachable!(),
         }
     }
+    import_stack.pop();
 
-    unreachable!()
+    // Relative paths declared in this file are relative to the file itself
+    theme.resolve_paths(
+        path.parent()
+            .expect("Theme file should have a parent directory"),
+    );
+
+    Ok(())
 }
 
 trait ApplyConfig {
     fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError>;
 }
 
-impl TryFrom<Pair<'_, Rule>> for Theme {
-    type Error = ConfigError;
-    fn try_from(pair: Pair<'_, Rule>) -> Result<Self, Self::Error> {
-        let mut theme = Theme::base();
-
+impl ApplyConfig for Theme {
+    fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError> {
         for pair in pair.into_inner() {
             match pair.as_rule() {
-                Rule::exit_unfocused => theme.exit_unfocused = helpers::unwrap_attr_bool(pair),
-                Rule::font_family => theme.font = Some(helpers::unwrap_attr_str(pair).to_string()),
+                Rule::exit_unfocused => self.exit_unfocused = helpers::unwrap_attr_bool(pair),
+                Rule::transition_duration => {
+                    self.transition_duration = helpers::unwrap_duration(pair)?
+                }
+                Rule::app_layout => self.layout = helpers::unwrap_app_layout(pair),
+                Rule::hover_select => self.hover_select = helpers::unwrap_attr_bool(pair),
+                Rule::wheel_select => self.wheel_select = helpers::unwrap_attr_bool(pair),
+                Rule::touch => self.touch = helpers::unwrap_attr_bool(pair),
+                Rule::on_screen_keyboard => {
+                    self.on_screen_keyboard = helpers::unwrap_attr_bool(pair)
+                }
+                Rule::click_activation => {
+                    self.click_activation = helpers::unwrap_click_activation(pair)
+                }
+                Rule::font_family => self.font = Some(helpers::unwrap_attr_str(pair).to_string()),
                 Rule::font_size => {
-                    theme.font_size = helpers::unwrap_attr_u16(pair)?;
-                    theme.propagate_font_size();
+                    self.font_size = helpers::unwrap_attr_u16(pair)?;
+                    self.propagate_font_size();
                 }
                 Rule::icon_theme => {
-                    theme.icon_theme = Some(helpers::unwrap_attr_str(pair).to_string())
+                    self.icon_theme = Some(helpers::unwrap_attr_str(pair).to_string())
                 }
                 Rule::icon_size => {
-                    theme.icon_size = helpers::unwrap_attr_u16(pair)?;
-                    theme.propagate_icon_size();
+                    self.icon_size = helpers::unwrap_attr_u16(pair)?;
+                    self.propagate_icon_size();
+                }
+                Rule::window_height => self.size.1 = helpers::unwrap_attr_32(pair)?,
+                Rule::window_width => self.size.0 = helpers::unwrap_attr_32(pair)?,
+                Rule::background_image => {
+                    self.background_image = Some(PathBuf::from(helpers::unwrap_attr_str(pair)))
                 }
-                Rule::window_height => theme.size.1 = helpers::unwrap_attr_32(pair)?,
-                Rule::window_width => theme.size.0 = helpers::unwrap_attr_32(pair)?,
                 Rule::background => {
-                    theme.background = helpers::unwrap_hex_color(pair)?;
-                    theme.propagate_background();
+                    self.background = helpers::unwrap_background(pair)?;
+                    self.propagate_background();
                 }
                 Rule::color => {
-                    theme.color = helpers::unwrap_hex_color(pair)?;
-                    theme.propagate_color();
+                    self.color = helpers::unwrap_color(pair)?;
+                    self.propagate_color();
                 }
-                Rule::border_color => theme.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_width => theme.border_width = helpers::unwrap_attr_f32(pair)?,
-                Rule::border_radius => theme.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::padding => {
-                    theme.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
+                    self.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
                 }
-                Rule::padding_top => theme.padding.top = helpers::unwrap_attr_u16(pair)?,
-                Rule::padding_bottom => theme.padding.bottom = helpers::unwrap_attr_u16(pair)?,
-                Rule::padding_right => theme.padding.right = helpers::unwrap_attr_u16(pair)?,
-                Rule::padding_left => theme.padding.left = helpers::unwrap_attr_u16(pair)?,
-                Rule::container => theme.app_container.apply(pair)?,
+                Rule::padding_top => self.padding.top = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_bottom => self.padding.bottom = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_right => self.padding.right = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_left => self.padding.left = helpers::unwrap_attr_u16(pair)?,
+                Rule::container => self.app_container.apply(pair)?,
                 Rule::EOI => break,
                 _ => unreachable!(),
             }
         }
 
-        Ok(theme)
+        Ok(())
     }
 }
 
@@ -106,17 +185,21 @@ impl ApplyConfig for AppContainerStyles {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Style
+                Rule::background_image => {
+                    self.background_image = Some(PathBuf::from(helpers::unwrap_attr_str(pair)))
+                }
                 Rule::background => {
-                    self.background = helpers::unwrap_hex_color(pair)?;
+                    self.background = helpers::unwrap_background(pair)?;
                     self.propagate_background();
                 }
                 Rule::color => {
-                    self.color = helpers::unwrap_hex_color(pair)?;
+                    self.color = helpers::unwrap_color(pair)?;
                     self.propagate_color();
                 }
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
                 // Padding
                 Rule::padding => {
                     self.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
@@ -128,7 +211,35 @@ impl ApplyConfig for AppContainerStyles {
                 // Children
                 Rule::search => self.search.apply(pair)?,
                 Rule::rows => self.rows.apply(pair)?,
+                Rule::mode_rows => self.mode_rows_overrides.push(ModeRowsOverride {
+                    source: pair.as_str().to_string(),
+                    dir: None,
+                }),
                 Rule::scrollable => self.scrollable.apply(pair)?,
+                Rule::preview => {
+                    self.preview = {
+                        let mut preview = PreviewStyles {
+                            background: self.background.inherited(),
+                            color: self.color,
+                            ..Default::default()
+                        };
+                        preview.propagate_background();
+                        preview.propagate_color();
+                        preview.apply(pair)?;
+                        Some(preview)
+                    }
+                }
+                Rule::status => {
+                    self.status = {
+                        let mut status = StatusStyles {
+                            background: self.background.inherited(),
+                            color: self.color,
+                            ..Default::default()
+                        };
+                        status.apply(pair)?;
+                        Some(status)
+                    }
+                }
                 _ => unreachable!(),
             }
         }
@@ -137,29 +248,67 @@ impl ApplyConfig for AppContainerStyles {
     }
 }
 
+impl AppContainerStyles {
+    // Mode overrides are applied once the whole theme is parsed, in declaration order,
+    // on top of the final base rows style, so later `.rows` blocks, imports
+    // and media queries reach them
+    fn apply_mode_rows(&mut self) -> Result<(), ConfigError> {
+        for mode_rows in std::mem::take(&mut self.mode_rows_overrides) {
+            let pair = ThemeParser::parse(Rule::mode_rows, &mode_rows.source)
+                .expect("Mode rows block should have been parsed already")
+                .next()
+                .unwrap();
+
+            let mut inner = pair.into_inner();
+            let mode = inner.next().unwrap();
+            let mode = match mode.as_rule() {
+                Rule::string => mode.into_inner().as_str().to_string(),
+                _ => mode.as_str().to_string(),
+            };
+
+            let base = &self.rows;
+            let rows = self.mode_rows.entry(mode).or_insert_with(|| base.clone());
+            let inherited_image = rows.background_image.clone();
+            rows.apply_pairs(inner)?;
+
+            // Only an image declared by this block is relative to its file
+            if let (Some(image), Some(dir)) = (&mut rows.background_image, &mode_rows.dir) {
+                if Some(&*image) != inherited_image.as_ref() {
+                    resolve_path(image, dir);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl ApplyConfig for ScrollerStyles {
     fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError> {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Style
-                Rule::background => self.background = helpers::unwrap_hex_color(pair)?,
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::background => self.background = helpers::unwrap_background(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
                 Rule::scrollbar_margin => self.scrollbar_margin = helpers::unwrap_attr_u16(pair)?,
+                Rule::transition_duration => {
+                    self.transition_duration = helpers::unwrap_duration(pair)?
+                }
                 Rule::scrollbar_width => self.scrollbar_width = helpers::unwrap_attr_u16(pair)?,
                 Rule::scroller => {
                     for pair in pair.into_inner() {
                         match pair.as_rule() {
-                            Rule::color => self.scroller_color = helpers::unwrap_hex_color(pair)?,
+                            Rule::color => self.scroller_color = helpers::unwrap_color(pair)?,
                             Rule::border_color => {
-                                self.scroller_border_color = helpers::unwrap_hex_color(pair)?
+                                self.scroller_border_color = helpers::unwrap_color(pair)?
                             }
                             Rule::border_width => {
                                 self.scroller_border_width = helpers::unwrap_attr_f32(pair)?
                             }
                             Rule::border_radius => {
-                                self.scroller_border_radius = helpers::unwrap_attr_f32(pair)?
+                                self.scroller_border_radius = helpers::unwrap_radius(pair)?
                             }
                             Rule::scroller_width => {
                                 self.scroller_width = helpers::unwrap_attr_u16(pair)?
@@ -181,17 +330,21 @@ impl ApplyConfig for SearchContainerStyles {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Style
+                Rule::background_image => {
+                    self.background_image = Some(PathBuf::from(helpers::unwrap_attr_str(pair)))
+                }
                 Rule::background => {
-                    self.background = helpers::unwrap_hex_color(pair)?;
+                    self.background = helpers::unwrap_background(pair)?;
                     self.propagate_background();
                 }
                 Rule::color => {
-                    self.color = helpers::unwrap_hex_color(pair)?;
+                    self.color = helpers::unwrap_color(pair)?;
                     self.propagate_color();
                 }
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
 
                 // Layout
                 Rule::padding => {
@@ -230,16 +383,14 @@ impl ApplyConfig for SearchInputStyles {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Style
-                Rule::background => self.background = helpers::unwrap_hex_color(pair)?,
-                Rule::color => self.value_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::background => self.background = helpers::unwrap_background(pair)?,
+                Rule::color => self.value_color = helpers::unwrap_color(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
                 Rule::text_width => self.text_width = helpers::unwrap_length(pair)?,
-                Rule::selection_color => self.selection_color = helpers::unwrap_hex_color(pair)?,
-                Rule::placeholder_color => {
-                    self.placeholder_color = helpers::unwrap_hex_color(pair)?
-                }
+                Rule::selection_color => self.selection_color = helpers::unwrap_color(pair)?,
+                Rule::placeholder_color => self.placeholder_color = helpers::unwrap_color(pair)?,
                 Rule::font_size => self.font_size = helpers::unwrap_attr_u16(pair)?,
 
                 // Layout
@@ -264,20 +415,30 @@ impl ApplyConfig for SearchInputStyles {
 
 impl ApplyConfig for RowContainerStyle {
     fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError> {
-        for pair in pair.into_inner() {
+        self.apply_pairs(pair.into_inner())
+    }
+}
+
+impl RowContainerStyle {
+    fn apply_pairs(&mut self, pairs: Pairs<'_, Rule>) -> Result<(), ConfigError> {
+        for pair in pairs {
             match pair.as_rule() {
                 // Style
                 Rule::color => {
-                    self.color = helpers::unwrap_hex_color(pair)?;
+                    self.color = helpers::unwrap_color(pair)?;
                     self.propagate_color();
                 }
+                Rule::background_image => {
+                    self.background_image = Some(PathBuf::from(helpers::unwrap_attr_str(pair)))
+                }
                 Rule::background => {
-                    self.background = helpers::unwrap_hex_color(pair)?;
+                    self.background = helpers::unwrap_background(pair)?;
                     self.propagate_color();
                 }
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
 
                 // Padding
                 Rule::padding => {
@@ -290,6 +451,11 @@ impl ApplyConfig for RowContainerStyle {
                 Rule::width => self.width = helpers::unwrap_length(pair)?,
                 Rule::height => self.height = helpers::unwrap_length(pair)?,
 
+                // Layout
+                Rule::layout => self.layout = helpers::unwrap_layout(pair),
+                Rule::columns => self.columns = Some(helpers::unwrap_attr_u16(pair)?),
+                Rule::cell_width => self.cell_width = Some(helpers::unwrap_attr_u16(pair)?),
+
                 // Children
                 Rule::default_row => self.row.apply(pair)?,
                 Rule::selected_row => self.row_selected.apply(pair)?,
@@ -308,14 +474,15 @@ impl ApplyConfig for RowStyles {
             match pair.as_rule() {
                 // Iced style
                 Rule::background => {
-                    self.background = helpers::unwrap_hex_color(pair)?;
+                    self.background = helpers::unwrap_background(pair)?;
                     self.propagate_background();
                 }
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
                 Rule::color => {
-                    self.color = helpers::unwrap_hex_color(pair)?;
+                    self.color = helpers::unwrap_color(pair)?;
                     self.propagate_color();
                 }
 
@@ -328,6 +495,9 @@ impl ApplyConfig for RowStyles {
                 Rule::padding_right => self.padding.right = helpers::unwrap_attr_u16(pair)?,
                 Rule::padding_left => self.padding.left = helpers::unwrap_attr_u16(pair)?,
                 Rule::spacing => self.spacing = helpers::unwrap_attr_u16(pair)?,
+                Rule::transition_duration => {
+                    self.transition_duration = helpers::unwrap_duration(pair)?
+                }
                 Rule::width => self.width = helpers::unwrap_length(pair)?,
                 Rule::height => self.height = helpers::unwrap_length(pair)?,
                 Rule::align_x => self.align_x = helpers::unwrap_x(pair)?,
@@ -352,16 +522,94 @@ impl ApplyConfig for RowStyles {
     }
 }
 
+impl ApplyConfig for PreviewStyles {
+    fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError> {
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                // Style
+                Rule::background => {
+                    self.background = helpers::unwrap_background(pair)?;
+                    self.propagate_background();
+                }
+                Rule::color => {
+                    self.color = helpers::unwrap_color(pair)?;
+                    self.propagate_color();
+                }
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
+                Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
+
+                // Layout
+                Rule::padding => {
+                    self.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
+                }
+                Rule::padding_top => self.padding.top = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_bottom => self.padding.bottom = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_right => self.padding.right = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_left => self.padding.left = helpers::unwrap_attr_u16(pair)?,
+                Rule::width => self.width = helpers::unwrap_length(pair)?,
+                Rule::height => self.height = helpers::unwrap_length(pair)?,
+                Rule::spacing => self.spacing = helpers::unwrap_attr_u16(pair)?,
+                Rule::font_size => self.font_size = helpers::unwrap_attr_u16(pair)?,
+                Rule::position => self.position = helpers::unwrap_position(pair),
+
+                // Children
+                Rule::title_row => self.title.apply(pair)?,
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ApplyConfig for StatusStyles {
+    fn apply(&mut self, pair: Pair<'_, Rule>) -> Result<(), ConfigError> {
+        for pair in pair.into_inner() {
+            match pair.as_rule() {
+                // Style
+                Rule::background => self.background = helpers::unwrap_background(pair)?,
+                Rule::color => self.color = helpers::unwrap_color(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
+                Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
+                Rule::box_shadow => self.box_shadow = helpers::unwrap_shadow(pair)?,
+
+                // Layout
+                Rule::padding => {
+                    self.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
+                }
+                Rule::padding_top => self.padding.top = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_bottom => self.padding.bottom = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_right => self.padding.right = helpers::unwrap_attr_u16(pair)?,
+                Rule::padding_left => self.padding.left = helpers::unwrap_attr_u16(pair)?,
+                Rule::width => self.width = helpers::unwrap_length(pair)?,
+                Rule::height => self.height = helpers::unwrap_length(pair)?,
+                Rule::spacing => self.spacing = helpers::unwrap_attr_u16(pair)?,
+                Rule::align_y => self.align_y = helpers::unwrap_y(pair)?,
+                Rule::font_size => self.font_size = helpers::unwrap_attr_u16(pair)?,
+
+                // Content
+                Rule::key_hints => self.key_hints = helpers::unwrap_attr_bool(pair),
+                _ => unreachable!(),
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl ApplyConfig for GenericContainerStyle {
     fn apply(&mut self, pair: Pair<Rule>) -> Result<(), ConfigError> {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Iced style
-                Rule::background => self.background = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::background => self.background = helpers::unwrap_background(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::color => self.color = helpers::unwrap_hex_color(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::color => self.color = helpers::unwrap_color(pair)?,
                 // Iced Layout
                 Rule::padding => {
                     self.padding = OnagrePadding::from(helpers::unwrap_attr_u16(pair)?)
@@ -388,11 +636,11 @@ impl ApplyConfig for IconStyle {
         for pair in pair.into_inner() {
             match pair.as_rule() {
                 // Iced style
-                Rule::background => self.background = helpers::unwrap_hex_color(pair)?,
-                Rule::border_radius => self.border_radius = helpers::unwrap_attr_f32(pair)?,
+                Rule::background => self.background = helpers::unwrap_background(pair)?,
+                Rule::border_radius => self.border_radius = helpers::unwrap_radius(pair)?,
                 Rule::border_width => self.border_width = helpers::unwrap_attr_f32(pair)?,
-                Rule::border_color => self.border_color = helpers::unwrap_hex_color(pair)?,
-                Rule::color => self.color = helpers::unwrap_hex_color(pair)?,
+                Rule::border_color => self.border_color = helpers::unwrap_color(pair)?,
+                Rule::color => self.color = helpers::unwrap_color(pair)?,
 
                 // Iced Layout
                 Rule::padding => {
@@ -415,7 +663,35 @@ impl ApplyConfig for IconStyle {
     }
 }
 
+// `~` is the home directory, relative paths are relative to `base`
+fn resolve_path(path: &mut PathBuf, base: &Path) {
+    if let Ok(relative) = path.strip_prefix("~") {
+        if let Some(home) = dirs::home_dir() {
+            *path = home.join(relative);
+        }
+    } else if path.is_relative() {
+        *path = base.join(&path);
+    }
+}
+
 impl Theme {
+    fn resolve_paths(&mut self, base: &Path) {
+        let images = [
+            &mut self.background_image,
+            &mut self.app_container.background_image,
+            &mut self.app_container.search.background_image,
+            &mut self.app_container.rows.background_image,
+        ];
+
+        for mode_rows in &mut self.app_container.mode_rows_overrides {
+            mode_rows.dir.get_or_insert_with(|| base.to_path_buf());
+        }
+
+        for image in IntoIterator::into_iter(images).flatten() {
+            resolve_path(image, base);
+        }
+    }
+
     fn base() -> Self {
         Theme {
             icon_theme: None,
@@ -439,3 +715,289 @@ impl Theme {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::app::style::preview::PreviewPosition;
+    use crate::app::style::scrollable::RowsLayout;
+    use crate::app::style::AppLayout;
+    use crate::config::background::OnagreBackground;
+    use crate::config::error::ConfigError;
+    use crate::config::{parse_file_for_scheme, parse_file_with_scheme};
+    use crate::freedesktop::settings::ColorScheme;
+    use iced::Length;
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_override_imported_theme() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("base")).unwrap();
+        std::fs::write(
+            dir.join("base").join("team.scss"),
+            ".onagre {\n  font-size: 12px;\n  background: #ffffff;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("theme.scss"),
+            "@import \"base/team.scss\";\n.onagre {\n  font-size: 20px;\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+
+        assert_that!(theme.font_size).is_equal_to(20);
+        assert_that!(theme.background).is_equal_to(OnagreBackground::WHITE);
+    }
+
+    #[test]
+    fn should_resolve_background_image_relative_to_imported_file() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("base")).unwrap();
+        std::fs::write(
+            dir.join("base").join("team.scss"),
+            ".onagre {\n  background-image: \"wallpaper.png\";\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("theme.scss"), "@import \"base/team.scss\";\n").unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+
+        assert_that!(theme.background_image).is_equal_to(Some(
+            dir.canonicalize()
+                .unwrap()
+                .join("base")
+                .join("wallpaper.png"),
+        ));
+    }
+
+    #[test]
+    fn should_detect_import_cycle() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("a.scss"), "@import \"b.scss\";\n").unwrap();
+        std::fs::write(dir.join("b.scss"), "@import \"a.scss\";\n").unwrap();
+
+        let theme = parse_file_for_scheme(dir.join("a.scss"), ColorScheme::NoPreference);
+
+        assert!(matches!(theme, Err(ConfigError::ImportCycle(_))));
+    }
+
+    #[test]
+    fn should_fail_on_missing_import() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("theme.scss"), "@import \"missing.scss\";\n").unwrap();
+
+        let theme = parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference);
+
+        assert!(matches!(theme, Err(ConfigError::ImportNotFound(_))));
+    }
+
+    #[test]
+    fn should_reject_color_amounts_out_of_range() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let theme = |amount: &str| {
+            let path = dir.join("theme.scss");
+            std::fs::write(
+                &path,
+                format!(".onagre {{\n  background: lighten(#808080, {amount});\n}}\n"),
+            )
+            .unwrap();
+            parse_file_for_scheme(path, ColorScheme::Dark)
+        };
+
+        assert_that!(theme("10%")).is_ok();
+        assert_that!(theme("0.1")).is_ok();
+        assert!(
+            matches!(theme("10"), Err(ConfigError::AmountOutOfRange(amount)) if amount == "10")
+        );
+        assert!(matches!(
+            theme("150%"),
+            Err(ConfigError::AmountOutOfRange(_))
+        ));
+    }
+
+    #[test]
+    fn should_apply_matching_color_scheme_block() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  font-size: 12px;\n  background: #000000;\n}\n\n\
+             @media (prefers-color-scheme: light) {\n  .onagre {\n    background: #ffffff;\n  }\n}\n",
+        )
+        .unwrap();
+
+        let dark = parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::Dark).unwrap();
+        let light = parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::Light).unwrap();
+
+        assert_that!(dark.background).is_equal_to(OnagreBackground::from(
+            crate::config::color::OnagreColor::from("#000000").unwrap(),
+        ));
+        assert_that!(light.background).is_equal_to(OnagreBackground::WHITE);
+        assert_that!(light.font_size).is_equal_to(12);
+    }
+
+    #[test]
+    fn should_not_read_color_scheme_without_media_queries() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("theme.scss");
+        std::fs::write(&path, ".onagre {\n  font-size: 12px;\n}\n").unwrap();
+
+        let theme = parse_file_with_scheme(&path, &|| panic!("Color scheme should not be read"));
+
+        assert_that!(theme.map(|theme| theme.font_size)).is_ok_containing(12);
+    }
+
+    #[test]
+    fn should_override_rows_style_per_mode() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  .container {\n\
+               .rows[mode=\"web\"] {\n      .row-selected {\n        background: #ffffff;\n      }\n    }\n\
+               .rows {\n      --height: fill;\n    }\n\
+               @plugin files {\n      background: #ffffff;\n    }\n\
+             }\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+        let rows = &theme.app_container.rows;
+        let web = &theme.app_container.mode_rows["web"];
+        let files = &theme.app_container.mode_rows["files"];
+
+        assert_that!(web.row_selected.background).is_equal_to(OnagreBackground::WHITE);
+        assert_that!(web.row).is_equal_to(&rows.row);
+        // Overrides apply on top of the base rows, whatever the declaration order
+        assert_that!(web.height).is_equal_to(rows.height);
+        assert_that!(files.background).is_equal_to(OnagreBackground::WHITE);
+        assert_that!(rows.row_selected.background).is_not_equal_to(OnagreBackground::WHITE);
+    }
+
+    #[test]
+    fn should_apply_mode_rows_on_the_final_base_rows() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("base.scss"),
+            ".onagre {\n  .container {\n\
+               @plugin web {\n      --height: fill;\n    }\n\
+             }\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("theme.scss"),
+            "@import \"base.scss\";\n\
+             .onagre {\n  color: #ffffff;\n  .container {\n\
+               .rows {\n      --width: 300px;\n    }\n\
+             }\n}\n",
+        )
+        .unwrap();
+
+        let theme = parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::Dark).unwrap();
+        let rows = &theme.app_container.rows;
+        let web = &theme.app_container.mode_rows["web"];
+
+        // The imported override sees the color and width set after it
+        assert_that!(web.width).is_equal_to(Length::Fixed(300.0));
+        assert_that!(web.color).is_equal_to(rows.color);
+        assert_that!(web.row).is_equal_to(&rows.row);
+        assert_that!(web.height).is_equal_to(Length::Fill);
+        assert_that!(rows.height).is_not_equal_to(Length::Fill);
+    }
+
+    #[test]
+    fn should_parse_grid_layout() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  .container {\n    .rows {\n\
+               --layout: grid;\n      --cell-width: 100px;\n      padding: 0;\n    }\n  }\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+        let rows = &theme.app_container.rows;
+
+        assert_that!(rows.layout).is_equal_to(RowsLayout::Grid);
+        assert_that!(rows.grid_columns(450.0)).is_equal_to(4);
+    }
+
+    #[test]
+    fn should_parse_bar_layout() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(dir.join("theme.scss"), ".onagre {\n  --layout: bar;\n}\n").unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+
+        assert_that!(theme.layout).is_equal_to(AppLayout::Bar);
+    }
+
+    #[test]
+    fn should_parse_preview_pane() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  .container {\n    background: #ffffff;\n    .preview {\n      --position: bottom;\n      --height: 120px;\n      .title {\n        font-size: 20px;\n      }\n    }\n  }\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+        let preview = theme.preview().unwrap();
+
+        assert_that!(preview.position).is_equal_to(PreviewPosition::Bottom);
+        assert_that!(preview.height).is_equal_to(iced::Length::Fixed(120.0));
+        assert_that!(preview.title.font_size).is_equal_to(20);
+        assert_that!(preview.background).is_equal_to(OnagreBackground::WHITE);
+    }
+
+    #[test]
+    fn should_parse_status_bar() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  .container {\n    color: #ffffff;\n    .status {\n      font-size: 10px;\n      --key-hints: false;\n    }\n  }\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+        let status = theme.status().unwrap();
+
+        assert_that!(status.font_size).is_equal_to(10);
+        assert_that!(status.key_hints).is_false();
+        assert_that!(status.color).is_equal_to(theme.app().color);
+    }
+
+    #[test]
+    fn should_parse_touch_profile() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("theme.scss"),
+            ".onagre {\n  --touch: true;\n  --on-screen-keyboard: true;\n}\n",
+        )
+        .unwrap();
+
+        let theme =
+            parse_file_for_scheme(dir.join("theme.scss"), ColorScheme::NoPreference).unwrap();
+
+        assert_that!(theme.touch).is_true();
+        assert_that!(theme.on_screen_keyboard).is_true();
+    }
+}
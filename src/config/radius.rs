@@ -0,0 +1,85 @@
+use crate::app::style::Scale;
+use iced_core::border::Radius;
+
+// Border radius for each corner, in the css order
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OnagreRadius {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl Eq for OnagreRadius {}
+
+impl Scale for OnagreRadius {
+    fn scale(self, scale: f32) -> Self {
+        OnagreRadius {
+            top_left: self.top_left * scale,
+            top_right: self.top_right * scale,
+            bottom_right: self.bottom_right * scale,
+            bottom_left: self.bottom_left * scale,
+        }
+    }
+}
+
+impl OnagreRadius {
+    pub const ZERO: OnagreRadius = OnagreRadius {
+        top_left: 0.0,
+        top_right: 0.0,
+        bottom_right: 0.0,
+        bottom_left: 0.0,
+    };
+
+    // Expand one to four css values to the four corners:
+    // - `all`
+    // - `top-left/bottom-right top-right/bottom-left`
+    // - `top-left top-right/bottom-left bottom-right`
+    // - `top-left top-right bottom-right bottom-left`
+    pub fn from_css(values: &[f32]) -> Self {
+        match *values {
+            [all] => OnagreRadius::from(all),
+            [top_left, top_right] => OnagreRadius {
+                top_left,
+                top_right,
+                bottom_right: top_left,
+                bottom_left: top_right,
+            },
+            [top_left, top_right, bottom_right] => OnagreRadius {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left: top_right,
+            },
+            [top_left, top_right, bottom_right, bottom_left, ..] => OnagreRadius {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            },
+            [] => OnagreRadius::ZERO,
+        }
+    }
+}
+
+impl From<f32> for OnagreRadius {
+    fn from(value: f32) -> Self {
+        OnagreRadius {
+            top_left: value,
+            top_right: value,
+            bottom_right: value,
+            bottom_left: value,
+        }
+    }
+}
+
+impl From<OnagreRadius> for Radius {
+    fn from(radius: OnagreRadius) -> Self {
+        Radius::from([
+            radius.top_left,
+            radius.top_right,
+            radius.bottom_right,
+            radius.bottom_left,
+        ])
+    }
+}
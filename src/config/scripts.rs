@@ -0,0 +1,125 @@
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use tracing::error;
+
+use crate::config::themes::config_dir;
+
+const SCRIPT_EXTENSION: &str = "ron";
+
+// How the output of a script command is read
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum ScriptFormat {
+    // One entry per line, an optional description follows a tab
+    #[default]
+    Lines,
+    // An array of `{ "name": ..., "description": ..., "icon": ..., "data": ... }` objects
+    Json,
+}
+
+// A mode backed by a script, declared in `$XDG_CONFIG_HOME/onagre/scripts/<name>.ron`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ScriptConfig {
+    pub prefix: String,
+    // Run with `sh -c`, the query is passed as `$1`
+    pub command: String,
+    #[serde(default)]
+    pub format: ScriptFormat,
+    // Command template run on activation, `{data}`, `{name}` and `{query}` are replaced
+    // by the shell quoted values. The entry data is copied when not set.
+    #[serde(default)]
+    pub exec: Option<String>,
+    #[serde(default)]
+    pub history: bool,
+    #[serde(default)]
+    pub icon: Option<String>,
+    // Run the command again on each query rather than filtering its first output
+    #[serde(default)]
+    pub dynamic: bool,
+}
+
+// A script config with the mode name, taken from its file name
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedScript {
+    pub name: String,
+    pub config: ScriptConfig,
+}
+
+// `$XDG_CONFIG_HOME/onagre/scripts`
+pub fn scripts_dir() -> Option<PathBuf> {
+    config_dir().map(|path| path.join("scripts"))
+}
+
+// Script modes sorted by name, invalid configs are reported and skipped
+pub fn list() -> Vec<NamedScript> {
+    scripts_dir().map(|dir| list_dir(&dir)).unwrap_or_default()
+}
+
+fn list_dir(dir: &Path) -> Vec<NamedScript> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut scripts: Vec<NamedScript> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some(SCRIPT_EXTENSION))
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            let config = std::fs::read_to_string(&path)
+                .map_err(|err| err.to_string())
+                .and_then(|content| {
+                    ron::from_str::<ScriptConfig>(&content).map_err(|err| err.to_string())
+                });
+
+            match config {
+                Ok(config) => Some(NamedScript { name, config }),
+                Err(err) => {
+                    error!("Invalid script mode {path:?}: {err}");
+                    None
+                }
+            }
+        })
+        .collect();
+
+    scripts.sort_by(|a, b| a.name.cmp(&b.name));
+    scripts
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::scripts::{list_dir, ScriptConfig, ScriptFormat};
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_list_script_configs() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::write(
+            dir.join("power.ron"),
+            r#"(
+                prefix: "power ",
+                command: "printf 'Lock\nSuspend\n'",
+                exec: Some("loginctl {data}"),
+                history: true,
+            )"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.ron"), "(prefix: 1)").unwrap();
+        std::fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let scripts = list_dir(dir);
+
+        assert_that!(scripts).has_length(1);
+        assert_that!(scripts[0].name).is_equal_to("power".to_string());
+        assert_that!(scripts[0].config).is_equal_to(ScriptConfig {
+            prefix: "power ".to_string(),
+            command: "printf 'Lock\nSuspend\n'".to_string(),
+            format: ScriptFormat::Lines,
+            exec: Some("loginctl {data}".to_string()),
+            history: true,
+            icon: None,
+            dynamic: false,
+        });
+    }
+}
@@ -0,0 +1,49 @@
+use crate::app::style::Scale;
+use crate::config::color::OnagreColor;
+use iced_core::{Shadow, Vector};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct OnagreShadow {
+    pub color: OnagreColor,
+    pub offset_x: f32,
+    pub offset_y: f32,
+    pub blur_radius: f32,
+}
+
+impl Eq for OnagreShadow {}
+
+impl Scale for OnagreShadow {
+    fn scale(self, scale: f32) -> Self {
+        OnagreShadow {
+            offset_x: self.offset_x * scale,
+            offset_y: self.offset_y * scale,
+            blur_radius: self.blur_radius * scale,
+            ..self
+        }
+    }
+}
+
+impl OnagreShadow {
+    pub const NONE: OnagreShadow = OnagreShadow {
+        color: OnagreColor::TRANSPARENT,
+        offset_x: 0.0,
+        offset_y: 0.0,
+        blur_radius: 0.0,
+    };
+}
+
+impl Default for OnagreShadow {
+    fn default() -> Self {
+        OnagreShadow::NONE
+    }
+}
+
+impl From<OnagreShadow> for Shadow {
+    fn from(shadow: OnagreShadow) -> Self {
+        Shadow {
+            color: shadow.color.into(),
+            offset: Vector::new(shadow.offset_x, shadow.offset_y),
+            blur_radius: shadow.blur_radius,
+        }
+    }
+}
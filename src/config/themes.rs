@@ -0,0 +1,142 @@
+use std::path::{Path, PathBuf};
+
+// The theme used when no named theme is selected, `$XDG_CONFIG_HOME/onagre/theme.scss`
+pub const DEFAULT_THEME: &str = "default";
+
+const THEME_EXTENSION: &str = "scss";
+
+// A theme file living in the onagre themes directory
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedTheme {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|path| path.join("onagre"))
+}
+
+// `$XDG_CONFIG_HOME/onagre/themes`
+pub fn themes_dir() -> Option<PathBuf> {
+    config_dir().map(|path| path.join("themes"))
+}
+
+// List the default theme, if any, and all themes in the themes directory sorted by name
+pub fn list() -> Vec<NamedTheme> {
+    let Some(config_dir) = config_dir() else {
+        return vec![];
+    };
+
+    let mut themes = list_dir(&config_dir.join("themes"));
+    let default = config_dir.join("theme.scss");
+    if default.exists() && !themes.iter().any(|theme| theme.name == DEFAULT_THEME) {
+        themes.insert(
+            0,
+            NamedTheme {
+                name: DEFAULT_THEME.to_string(),
+                path: default,
+            },
+        );
+    }
+
+    themes
+}
+
+fn list_dir(dir: &Path) -> Vec<NamedTheme> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    let mut themes: Vec<NamedTheme> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        // Skip light and dark variants, they are picked up when loading their base theme
+        .filter(|path| {
+            path.extension().and_then(|ext| ext.to_str()) == Some(THEME_EXTENSION)
+                && path
+                    .file_stem()
+                    .map(|stem| Path::new(stem).extension().is_none())
+                    .unwrap_or(false)
+        })
+        .filter_map(|path| {
+            let name = path.file_stem()?.to_string_lossy().to_string();
+            Some(NamedTheme { name, path })
+        })
+        .collect();
+
+    themes.sort_by(|a, b| a.name.cmp(&b.name));
+    themes
+}
+
+// Resolve a theme either from a path or from a theme name in the themes directory
+pub fn resolve(name_or_path: &str) -> Option<PathBuf> {
+    // A `nord` file in the working directory must not shadow the `nord` theme
+    if name_or_path.contains('/') || name_or_path.ends_with(".scss") {
+        let path = Path::new(name_or_path);
+        return path.is_file().then(|| path.to_path_buf());
+    }
+
+    list()
+        .into_iter()
+        .find(|theme| theme.name == name_or_path)
+        .map(|theme| theme.path)
+}
+
+fn current_theme_file() -> Option<PathBuf> {
+    config_dir().map(|path| path.join("current-theme"))
+}
+
+// The theme name persisted by the theme picker
+pub fn current() -> Option<String> {
+    current_theme_file()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
+pub fn persist(name: &str) -> std::io::Result<()> {
+    let path = current_theme_file().ok_or_else(|| {
+        std::io::Error::new(std::io::ErrorKind::NotFound, "Config directory not found")
+    })?;
+
+    std::fs::create_dir_all(path.parent().expect("Config file should have a parent"))?;
+    std::fs::write(path, name)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config::themes::{list_dir, resolve};
+    use speculoos::prelude::*;
+
+    #[test]
+    fn should_list_themes_without_variants() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        for file in [
+            "nord.scss",
+            "nord.dark.scss",
+            "catppuccin.scss",
+            "notes.txt",
+        ] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+
+        let themes = list_dir(dir);
+        let names: Vec<&str> = themes.iter().map(|theme| theme.name.as_str()).collect();
+
+        assert_that!(names).is_equal_to(vec!["catppuccin", "nord"]);
+    }
+
+    #[test]
+    fn should_only_resolve_paths_with_a_slash_or_extension() {
+        let temp = tempfile::tempdir().unwrap();
+        let theme = temp.path().join("nord.scss");
+        std::fs::write(&theme, "").unwrap();
+        let theme = theme.to_string_lossy();
+
+        assert_that!(resolve(&theme)).is_some();
+        assert_that!(resolve(&theme.replace(".scss", ".css"))).is_none();
+        // Files in the working directory are not themes unless given as paths
+        assert_that!(resolve("Cargo.toml")).is_none();
+    }
+}
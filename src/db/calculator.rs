@@ -0,0 +1,36 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::db::{Database, Entity};
+
+pub const COLLECTION: &str = "calculator";
+
+const ANSWER_KEY: &str = "ans";
+
+// The result of the last activated calculation
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AnswerEntity {
+    pub value: f64,
+}
+
+impl AnswerEntity {
+    pub fn get(db: &Database) -> Option<f64> {
+        db.get_by_key::<AnswerEntity>(COLLECTION, ANSWER_KEY)
+            .map(|answer| answer.value)
+    }
+
+    pub fn persist(value: f64, db: &Database) -> Result<(), redb::Error> {
+        db.insert(COLLECTION, &AnswerEntity { value })
+    }
+}
+
+impl<'a> Entity<'a> for AnswerEntity {
+    fn get_key(&self) -> Cow<'a, str> {
+        Cow::Borrowed(ANSWER_KEY)
+    }
+
+    fn get_weight(&self) -> u8 {
+        0
+    }
+}
@@ -0,0 +1,93 @@
+use std::borrow::Cow;
+use std::cmp::Reverse;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::db::{Database, Entity};
+
+pub const COLLECTION: &str = "clipboard";
+
+// Unpinned entries kept in the history, older ones are dropped
+pub const MAX_ENTRIES: usize = 200;
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClipboardEntity<'a> {
+    // Hash of the content
+    pub key: Cow<'a, str>,
+    // The copied text, or the path of the copied image
+    pub content: Cow<'a, str>,
+    pub image: bool,
+    pub pinned: bool,
+    // Milliseconds since the epoch of the last copy
+    pub copied_at: u64,
+}
+
+impl<'a> Entity<'a> for ClipboardEntity<'a> {
+    fn get_key(&self) -> Cow<'a, str> {
+        self.key.clone()
+    }
+
+    fn get_weight(&self) -> u8 {
+        self.pinned as u8
+    }
+}
+
+impl ClipboardEntity<'_> {
+    // Pinned entries first, then the most recently copied
+    pub fn all(db: &Database) -> Vec<ClipboardEntity<'static>> {
+        let mut entries = db.get_all::<ClipboardEntity>(COLLECTION);
+        entries.sort_by_key(|entry| (Reverse(entry.pinned), Reverse(entry.copied_at)));
+        entries
+    }
+
+    // Insert the entry, or move it to the top when it was already copied,
+    // then drop the entries over the history bounds
+    pub fn persist(&self, db: &Database) -> Result<(), redb::Error> {
+        let pinned = db
+            .get_by_key::<ClipboardEntity>(COLLECTION, &self.key)
+            .map(|entry| entry.pinned)
+            .unwrap_or(self.pinned);
+
+        let entity = ClipboardEntity {
+            pinned,
+            ..self.clone()
+        };
+
+        debug!("Inserting clipboard entry {}", entity.key);
+        db.insert(COLLECTION, &entity)?;
+
+        for entry in overflow(ClipboardEntity::all(db), MAX_ENTRIES) {
+            entry.delete(db)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn set_pinned(&self, pinned: bool, db: &Database) -> Result<(), redb::Error> {
+        let entity = ClipboardEntity {
+            pinned,
+            ..self.clone()
+        };
+
+        db.insert(COLLECTION, &entity)
+    }
+
+    // Remove the entry, and the image file it refers to
+    pub fn delete(&self, db: &Database) -> Result<(), redb::Error> {
+        if self.image {
+            let _ = std::fs::remove_file(self.content.as_ref());
+        }
+
+        db.remove(COLLECTION, &self.key)
+    }
+}
+
+// Unpinned entries beyond the `max` most recent ones, sorted like `ClipboardEntity::all`
+pub fn overflow<'a>(entries: Vec<ClipboardEntity<'a>>, max: usize) -> Vec<ClipboardEntity<'a>> {
+    entries
+        .into_iter()
+        .filter(|entry| !entry.pinned)
+        .skip(max)
+        .collect()
+}
@@ -2,6 +2,7 @@ use once_cell::sync::Lazy;
 use std::borrow::Cow;
 use std::cmp::Reverse;
 use std::fmt::Debug;
+use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, trace};
 
@@ -9,6 +10,8 @@ use redb::{ReadableTable, TableDefinition};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+pub mod calculator;
+pub mod clipboard;
 pub mod desktop_entry;
 pub mod plugin;
 pub mod web;
@@ -22,25 +25,30 @@ pub struct Database {
 
 impl Default for Database {
     fn default() -> Self {
-        let path = dirs::data_dir().expect("Cannot open data dir");
+        Database::open().expect("failed to create database")
+    }
+}
 
-        let path = path.join("onagre-db");
-        let path = path.as_path();
+impl Database {
+    // Open the database, fails when another onagre process has it open
+    pub fn open() -> Result<Self, redb::DatabaseError> {
+        let path = dirs::data_dir().expect("Cannot open data dir");
+        Database::open_at(&path.join("onagre-db"))
+    }
 
+    pub fn open_at(path: &Path) -> Result<Self, redb::DatabaseError> {
         debug!("Opening database {:?}", path);
 
         let database = match redb::Database::open(path) {
             Ok(db) => db,
-            Err(_err) => redb::Database::create(path).expect("failed to create database"),
+            Err(_err) => redb::Database::create(path)?,
         };
 
-        Database {
+        Ok(Database {
             inner: Arc::new(database),
-        }
+        })
     }
-}
 
-impl Database {
     pub fn insert<'a, T>(&self, collection: &str, entity: &T) -> Result<(), redb::Error>
     where
         T: Sized + Entity<'a> + Serialize,
@@ -57,6 +65,18 @@ impl Database {
         Ok(())
     }
 
+    pub fn remove(&self, collection: &str, key: &str) -> Result<(), redb::Error> {
+        let db = self.inner.clone();
+        let write_tnx = db.begin_write()?;
+        {
+            let definition = TableDefinition::<&str, &str>::new(collection);
+            let mut table = write_tnx.open_table(definition)?;
+            table.remove(key)?;
+        }
+        write_tnx.commit()?;
+        Ok(())
+    }
+
     pub fn get_by_key<'a, T>(&self, collection: &str, key: &str) -> Option<T>
     where
         T: Entity<'a> + DeserializeOwned,
@@ -0,0 +1,85 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use tracing::error;
+
+// MIME types password managers offer along with the secrets they copy
+const PASSWORD_MANAGER_HINTS: [&str; 3] = [
+    "x-kde-passwordManagerHint",
+    "org.nspasteboard.ConcealedType",
+    "org.nspasteboard.TransientType",
+];
+
+// Onagre exits right after copying, the clipboard content has to be served by
+// another process: wl-copy on Wayland, xclip on X11. Both fork to the background.
+pub fn copy_to_clipboard(text: &str) {
+    copy(text.as_bytes(), None);
+}
+
+pub fn copy_image_to_clipboard(path: &Path, mime_type: &str) {
+    match std::fs::read(path) {
+        Ok(image) => copy(&image, Some(mime_type)),
+        Err(err) => error!("Unable to read {path:?}: {err}"),
+    }
+}
+
+// Whether the clipboard content comes from a password manager
+pub fn is_sensitive<S: AsRef<str>>(mime_types: &[S]) -> bool {
+    mime_types
+        .iter()
+        .any(|mime_type| PASSWORD_MANAGER_HINTS.contains(&mime_type.as_ref().trim()))
+}
+
+// MIME types offered by the current clipboard owner, only known on Wayland
+pub fn offered_mime_types() -> Vec<String> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        return vec![];
+    }
+
+    Command::new("wl-paste")
+        .arg("--list-types")
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn copy(content: &[u8], mime_type: Option<&str>) {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let (program, mut args): (&str, Vec<&str>) = if wayland {
+        ("wl-copy", vec![])
+    } else {
+        ("xclip", vec!["-selection", "clipboard"])
+    };
+
+    if let Some(mime_type) = mime_type {
+        args.extend([if wayland { "--type" } else { "-t" }, mime_type]);
+    }
+
+    let child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(err) => {
+            error!("Unable to copy to the clipboard with {program}: {err}");
+            return;
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        if let Err(err) = stdin.write_all(content) {
+            error!("Unable to copy to the clipboard with {program}: {err}");
+        }
+    }
+
+    let _ = child.wait();
+}
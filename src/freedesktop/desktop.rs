@@ -18,6 +18,14 @@ pub struct DesktopEntry<'a> {
     pub actions: Option<Cow<'a, str>>,
     pub comment: Option<Cow<'a, str>>,
     pub keywords: Option<Cow<'a, str>>,
+    pub categories: Option<Cow<'a, str>>,
+    #[serde(rename = "Type")]
+    pub kind: Option<Cow<'a, str>>,
+    pub no_display: Option<Cow<'a, str>>,
+    pub hidden: Option<Cow<'a, str>>,
+    pub only_show_in: Option<Cow<'a, str>>,
+    pub not_show_in: Option<Cow<'a, str>>,
+    pub terminal: Option<Cow<'a, str>>,
 }
 
 impl DesktopEntry<'_> {
@@ -29,4 +37,35 @@ impl DesktopEntry<'_> {
             .flatten()
             .map(|ini| ini.content)
     }
+
+    // Hidden entries are uninstalled ones, `NoDisplay` entries only exist to handle mime types
+    pub fn is_displayed(&self, current_desktops: &[String]) -> bool {
+        let is_true = |value: &Option<Cow<str>>| value.as_deref() == Some("true");
+        let desktops = |value: &Option<Cow<str>>| -> Vec<String> {
+            value
+                .as_deref()
+                .map(|desktops| split_list(desktops).map(str::to_string).collect())
+                .unwrap_or_default()
+        };
+
+        let shown_in = |value: &Option<Cow<str>>| {
+            desktops(value)
+                .iter()
+                .any(|desktop| current_desktops.contains(desktop))
+        };
+
+        self.kind.as_deref().unwrap_or("Application") == "Application"
+            && !is_true(&self.no_display)
+            && !is_true(&self.hidden)
+            && (self.only_show_in.is_none() || shown_in(&self.only_show_in))
+            && !shown_in(&self.not_show_in)
+    }
+}
+
+// Split a `;` separated desktop entry value
+pub fn split_list(value: &str) -> impl Iterator<Item = &str> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
 }
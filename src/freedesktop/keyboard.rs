@@ -0,0 +1,45 @@
+use std::process::{Command, Stdio};
+
+use tracing::{debug, error};
+
+// Ask the on-screen keyboard to show up. Only squeekboard exposes a D-Bus
+// interface for that, it is called through busctl to avoid pulling a dbus dependency.
+pub fn show_on_screen_keyboard() {
+    let spawned = Command::new("busctl")
+        .args([
+            "--user",
+            "call",
+            "sm.puri.OSK0",
+            "/sm/puri/OSK0",
+            "sm.puri.OSK0",
+            "SetVisible",
+            "b",
+            "true",
+        ])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+
+    if let Err(err) = spawned {
+        debug!("Unable to request the on-screen keyboard: {err}");
+    }
+}
+
+// Type the text with wtype on Wayland, xdotool on X11. Typing starts once onagre exited,
+// so the keystrokes go to the previously focused window.
+pub fn type_text(text: &str) {
+    let program = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        "sleep 0.2 && wtype -- \"$0\""
+    } else {
+        "sleep 0.2 && xdotool type --clearmodifiers -- \"$0\""
+    };
+
+    let spawned = Command::new("sh")
+        .args(["-c", program, text])
+        .stdout(Stdio::null())
+        .spawn();
+
+    if let Err(err) = spawned {
+        error!("Unable to type {text}: {err}");
+    }
+}
@@ -1 +1,5 @@
+pub mod clipboard;
 pub mod desktop;
+pub mod keyboard;
+pub mod settings;
+pub mod toplevel;
@@ -0,0 +1,216 @@
+use once_cell::sync::OnceCell;
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tracing::debug;
+
+// Settings are read at startup and when focused again, an unresponsive bus must not delay onagre for long
+const COMMAND_TIMEOUT: Duration = Duration::from_millis(500);
+
+static COLOR_SCHEME: Mutex<Option<ColorScheme>> = Mutex::new(None);
+static REDUCED_MOTION: OnceCell<bool> = OnceCell::new();
+
+// Overrides the detected color scheme, "dark" or "light"
+pub const COLOR_SCHEME_ENV: &str = "ONAGRE_COLOR_SCHEME";
+
+// Overrides the detected reduced motion setting, "true" or "false"
+pub const REDUCED_MOTION_ENV: &str = "ONAGRE_REDUCED_MOTION";
+
+// Whether animations should be disabled, detected once
+pub fn prefers_reduced_motion() -> bool {
+    *REDUCED_MOTION.get_or_init(|| {
+        let reduced_motion = reduced_motion_from_env()
+            .or_else(|| read_portal_setting("reduced-motion").map(|value| value == 1))
+            .or_else(|| {
+                read_gsettings("enable-animations").map(|enabled| enabled.as_str() == "false")
+            })
+            .unwrap_or(false);
+        debug!("Reduced motion: {reduced_motion}");
+        reduced_motion
+    })
+}
+
+fn reduced_motion_from_env() -> Option<bool> {
+    match std::env::var(REDUCED_MOTION_ENV)
+        .ok()?
+        .to_lowercase()
+        .as_str()
+    {
+        "true" | "1" => Some(true),
+        "false" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+// Query the settings portal through busctl to avoid pulling a dbus dependency,
+// the reply looks like `v u 1` (or `v v u 1` with the deprecated `Read` method)
+fn read_portal_setting(key: &str) -> Option<u32> {
+    ["ReadOne", "Read"].iter().find_map(|method| {
+        let output = output_within_timeout(Command::new("busctl").args([
+            "--user",
+            "call",
+            "org.freedesktop.portal.Desktop",
+            "/org/freedesktop/portal/desktop",
+            "org.freedesktop.portal.Settings",
+            method,
+            "ss",
+            "org.freedesktop.appearance",
+            key,
+        ]))?;
+
+        parse_portal_reply(&output)
+    })
+}
+
+fn parse_portal_reply(reply: &str) -> Option<u32> {
+    reply.split_whitespace().last()?.parse::<u32>().ok()
+}
+
+fn read_gsettings(key: &str) -> Option<String> {
+    let output = output_within_timeout(Command::new("gsettings").args([
+        "get",
+        "org.gnome.desktop.interface",
+        key,
+    ]))?;
+    Some(output.trim().trim_matches('\'').to_string())
+}
+
+// The output of a successful command, it is killed after `COMMAND_TIMEOUT`
+fn output_within_timeout(command: &mut Command) -> Option<String> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+
+    let started = Instant::now();
+    loop {
+        match child.try_wait().ok()? {
+            Some(status) if status.success() => break,
+            Some(_) => return None,
+            None if started.elapsed() > COMMAND_TIMEOUT => {
+                debug!("{command:?} timed out");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => std::thread::sleep(Duration::from_millis(10)),
+        }
+    }
+
+    let mut output = String::new();
+    child.stdout.take()?.read_to_string(&mut output).ok()?;
+    Some(output)
+}
+
+// The freedesktop `org.freedesktop.appearance color-scheme` setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorScheme {
+    #[default]
+    NoPreference,
+    Dark,
+    Light,
+}
+
+impl ColorScheme {
+    // The system color scheme, detected on the first call
+    pub fn current() -> ColorScheme {
+        let mut current = COLOR_SCHEME.lock().unwrap();
+        *current.get_or_insert_with(ColorScheme::detect)
+    }
+
+    // Detect the system color scheme again, it may have changed since the last call
+    pub fn refresh() -> ColorScheme {
+        let scheme = ColorScheme::detect();
+        *COLOR_SCHEME.lock().unwrap() = Some(scheme);
+        scheme
+    }
+
+    fn detect() -> ColorScheme {
+        let scheme = ColorScheme::from_env()
+            .or_else(ColorScheme::from_portal)
+            .or_else(ColorScheme::from_gsettings)
+            .unwrap_or_default();
+        debug!("Using color scheme {scheme:?}");
+        scheme
+    }
+
+    // Like CSS `prefers-color-scheme`, no preference is treated as light
+    pub fn is_dark(self) -> bool {
+        self == ColorScheme::Dark
+    }
+
+    // Whether a `prefers-color-scheme` media query value matches this scheme
+    pub fn matches(self, query: &str) -> bool {
+        match query {
+            "dark" => self.is_dark(),
+            "light" => !self.is_dark(),
+            _ => false,
+        }
+    }
+
+    fn from_env() -> Option<ColorScheme> {
+        match std::env::var(COLOR_SCHEME_ENV)
+            .ok()?
+            .to_lowercase()
+            .as_str()
+        {
+            "dark" => Some(ColorScheme::Dark),
+            "light" => Some(ColorScheme::Light),
+            _ => None,
+        }
+    }
+
+    fn from_portal() -> Option<ColorScheme> {
+        read_portal_setting("color-scheme").map(ColorScheme::from_portal_value)
+    }
+
+    fn from_portal_value(value: u32) -> ColorScheme {
+        match value {
+            1 => ColorScheme::Dark,
+            2 => ColorScheme::Light,
+            _ => ColorScheme::NoPreference,
+        }
+    }
+
+    fn from_gsettings() -> Option<ColorScheme> {
+        match read_gsettings("color-scheme")?.as_str() {
+            "prefer-dark" => Some(ColorScheme::Dark),
+            "prefer-light" => Some(ColorScheme::Light),
+            _ => Some(ColorScheme::NoPreference),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::freedesktop::settings::{output_within_timeout, parse_portal_reply, ColorScheme};
+    use speculoos::prelude::*;
+    use std::process::Command;
+
+    #[test]
+    fn should_parse_portal_reply() {
+        assert_that!(parse_portal_reply("v u 1\n")).is_equal_to(Some(1));
+        assert_that!(parse_portal_reply("v v u 2\n")).is_equal_to(Some(2));
+        assert_that!(ColorScheme::from_portal_value(1)).is_equal_to(ColorScheme::Dark);
+        assert_that!(ColorScheme::from_portal_value(0)).is_equal_to(ColorScheme::NoPreference);
+    }
+
+    #[test]
+    fn no_preference_should_match_light_queries() {
+        assert_that!(ColorScheme::NoPreference.matches("light")).is_true();
+        assert_that!(ColorScheme::NoPreference.matches("dark")).is_false();
+        assert_that!(ColorScheme::Dark.matches("dark")).is_true();
+    }
+
+    #[test]
+    fn should_kill_commands_running_too_long() {
+        let sleep = output_within_timeout(Command::new("sleep").arg("5"));
+        let echo = output_within_timeout(Command::new("echo").arg("dark"));
+
+        assert_that!(sleep).is_none();
+        assert_that!(echo).is_equal_to(Some("dark\n".to_string()));
+    }
+}
@@ -0,0 +1,57 @@
+use std::fmt::Debug;
+
+use thiserror::Error;
+use tracing::warn;
+
+pub mod wayland;
+pub mod x11;
+
+// An open window, as reported by the compositor or the window manager
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Toplevel {
+    // Only valid for the provider it comes from
+    pub id: u64,
+    pub title: String,
+    // Wayland app ID, or the X11 window class
+    pub app_id: String,
+    // Whether the window has the focus
+    pub active: bool,
+}
+
+#[derive(Debug, Error)]
+pub enum ToplevelError {
+    #[error("Unable to connect to the display server: {0}")]
+    Connection(String),
+    #[error("Window management is not supported: {0}")]
+    Unsupported(String),
+    #[error("Unknown window {0}")]
+    UnknownWindow(u64),
+}
+
+// Lists, focuses and closes the open windows
+pub trait ToplevelProvider: Debug {
+    fn toplevels(&mut self) -> Result<Vec<Toplevel>, ToplevelError>;
+
+    fn focus(&mut self, id: u64) -> Result<(), ToplevelError>;
+
+    fn close(&mut self, id: u64) -> Result<(), ToplevelError>;
+}
+
+// wlr-foreign-toplevel-management on Wayland, EWMH on X11
+pub fn connect() -> Option<Box<dyn ToplevelProvider>> {
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        match wayland::WlrToplevels::connect() {
+            Ok(provider) => return Some(Box::new(provider)),
+            Err(err) => warn!("Wayland window listing unavailable: {err}"),
+        }
+    }
+
+    if std::env::var_os("DISPLAY").is_some() {
+        match x11::EwmhToplevels::connect() {
+            Ok(provider) => return Some(Box::new(provider)),
+            Err(err) => warn!("X11 window listing unavailable: {err}"),
+        }
+    }
+
+    None
+}
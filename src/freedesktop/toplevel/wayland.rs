@@ -0,0 +1,211 @@
+use std::collections::BTreeMap;
+
+use wayland_client::globals::{registry_queue_init, GlobalListContents};
+use wayland_client::protocol::wl_registry::WlRegistry;
+use wayland_client::protocol::wl_seat::WlSeat;
+use wayland_client::{event_created_child, Connection, Dispatch, EventQueue, Proxy, QueueHandle};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_handle_v1::{
+    self, ZwlrForeignToplevelHandleV1,
+};
+use wayland_protocols_wlr::foreign_toplevel::v1::client::zwlr_foreign_toplevel_manager_v1::{
+    self, ZwlrForeignToplevelManagerV1,
+};
+
+use crate::freedesktop::toplevel::{Toplevel, ToplevelError, ToplevelProvider};
+
+// `zwlr_foreign_toplevel_handle_v1.state` value of the focused window
+const STATE_ACTIVATED: u32 = 2;
+
+#[derive(Debug)]
+struct Handle {
+    proxy: ZwlrForeignToplevelHandleV1,
+    // Properties are double buffered, they apply on `done`
+    pending: Toplevel,
+    current: Option<Toplevel>,
+}
+
+// Toplevel handles, keyed by their protocol ID
+#[derive(Debug, Default)]
+struct Handles {
+    handles: BTreeMap<u64, Handle>,
+}
+
+// Windows of wlroots based compositors, through wlr-foreign-toplevel-management
+#[derive(Debug)]
+pub struct WlrToplevels {
+    connection: Connection,
+    queue: EventQueue<Handles>,
+    handles: Handles,
+    seat: Option<WlSeat>,
+    _manager: ZwlrForeignToplevelManagerV1,
+}
+
+impl WlrToplevels {
+    pub fn connect() -> Result<Self, ToplevelError> {
+        let connection = Connection::connect_to_env()
+            .map_err(|err| ToplevelError::Connection(err.to_string()))?;
+        let (globals, queue) = registry_queue_init::<Handles>(&connection)
+            .map_err(|err| ToplevelError::Connection(err.to_string()))?;
+
+        let qh = queue.handle();
+        let manager = globals
+            .bind::<ZwlrForeignToplevelManagerV1, _, _>(&qh, 1..=3, ())
+            .map_err(|err| ToplevelError::Unsupported(err.to_string()))?;
+        let seat = globals.bind::<WlSeat, _, _>(&qh, 1..=1, ()).ok();
+
+        let mut toplevels = WlrToplevels {
+            connection,
+            queue,
+            handles: Handles::default(),
+            seat,
+            _manager: manager,
+        };
+
+        // The first roundtrip announces the toplevels, the second one their properties
+        toplevels.roundtrip()?;
+        toplevels.roundtrip()?;
+        Ok(toplevels)
+    }
+
+    fn roundtrip(&mut self) -> Result<(), ToplevelError> {
+        self.queue
+            .roundtrip(&mut self.handles)
+            .map(|_| ())
+            .map_err(|err| ToplevelError::Connection(err.to_string()))
+    }
+
+    fn handle(&self, id: u64) -> Result<&ZwlrForeignToplevelHandleV1, ToplevelError> {
+        self.handles
+            .handles
+            .get(&id)
+            .map(|handle| &handle.proxy)
+            .ok_or(ToplevelError::UnknownWindow(id))
+    }
+
+    fn flush(&self) -> Result<(), ToplevelError> {
+        self.connection
+            .flush()
+            .map_err(|err| ToplevelError::Connection(err.to_string()))
+    }
+}
+
+impl ToplevelProvider for WlrToplevels {
+    fn toplevels(&mut self) -> Result<Vec<Toplevel>, ToplevelError> {
+        self.roundtrip()?;
+        Ok(self
+            .handles
+            .handles
+            .values()
+            .filter_map(|handle| handle.current.clone())
+            .collect())
+    }
+
+    fn focus(&mut self, id: u64) -> Result<(), ToplevelError> {
+        let seat = self
+            .seat
+            .as_ref()
+            .ok_or_else(|| ToplevelError::Unsupported("no seat to focus windows".to_string()))?;
+        self.handle(id)?.activate(seat);
+        self.flush()
+    }
+
+    fn close(&mut self, id: u64) -> Result<(), ToplevelError> {
+        self.handle(id)?.close();
+        self.flush()?;
+        self.roundtrip()
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for Handles {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: <WlRegistry as Proxy>::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlSeat, ()> for Handles {
+    fn event(
+        _: &mut Self,
+        _: &WlSeat,
+        _: <WlSeat as Proxy>::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<ZwlrForeignToplevelManagerV1, ()> for Handles {
+    fn event(
+        state: &mut Self,
+        _: &ZwlrForeignToplevelManagerV1,
+        event: zwlr_foreign_toplevel_manager_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let zwlr_foreign_toplevel_manager_v1::Event::Toplevel { toplevel } = event {
+            let id = toplevel.id().protocol_id() as u64;
+            state.handles.insert(
+                id,
+                Handle {
+                    proxy: toplevel,
+                    pending: Toplevel {
+                        id,
+                        ..Default::default()
+                    },
+                    current: None,
+                },
+            );
+        }
+    }
+
+    event_created_child!(Handles, ZwlrForeignToplevelManagerV1, [
+        zwlr_foreign_toplevel_manager_v1::EVT_TOPLEVEL_OPCODE => (ZwlrForeignToplevelHandleV1, ())
+    ]);
+}
+
+impl Dispatch<ZwlrForeignToplevelHandleV1, ()> for Handles {
+    fn event(
+        state: &mut Self,
+        proxy: &ZwlrForeignToplevelHandleV1,
+        event: zwlr_foreign_toplevel_handle_v1::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        let id = proxy.id().protocol_id() as u64;
+        if let zwlr_foreign_toplevel_handle_v1::Event::Closed = event {
+            if let Some(handle) = state.handles.remove(&id) {
+                handle.proxy.destroy();
+            }
+            return;
+        }
+
+        let Some(handle) = state.handles.get_mut(&id) else {
+            return;
+        };
+
+        match event {
+            zwlr_foreign_toplevel_handle_v1::Event::Title { title } => handle.pending.title = title,
+            zwlr_foreign_toplevel_handle_v1::Event::AppId { app_id } => {
+                handle.pending.app_id = app_id
+            }
+            zwlr_foreign_toplevel_handle_v1::Event::State { state } => {
+                handle.pending.active = state
+                    .chunks_exact(4)
+                    .map(|value| u32::from_ne_bytes([value[0], value[1], value[2], value[3]]))
+                    .any(|value| value == STATE_ACTIVATED);
+            }
+            zwlr_foreign_toplevel_handle_v1::Event::Done => {
+                handle.current = Some(handle.pending.clone())
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::convert::TryFrom;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{AtomEnum, ClientMessageEvent, ConnectionExt, EventMask, Window};
+use x11rb::rust_connection::RustConnection;
+use x11rb::CURRENT_TIME;
+
+use crate::freedesktop::toplevel::{Toplevel, ToplevelError, ToplevelProvider};
+
+// Requests sent on behalf of a pager rather than of an application,
+// window managers honor them without focus stealing prevention
+const SOURCE_PAGER: u32 = 2;
+
+// Longest window title read, in 32 bits units
+const TITLE_LENGTH: u32 = 1024;
+
+x11rb::atom_manager! {
+    Atoms: AtomsCookie {
+        _NET_CLIENT_LIST,
+        _NET_ACTIVE_WINDOW,
+        _NET_CLOSE_WINDOW,
+        _NET_WM_NAME,
+        UTF8_STRING,
+    }
+}
+
+// Windows managed by an EWMH compliant X11 window manager
+#[derive(Debug)]
+pub struct EwmhToplevels {
+    connection: RustConnection,
+    root: Window,
+    atoms: Atoms,
+}
+
+fn connection_error<E: ToString>(err: E) -> ToplevelError {
+    ToplevelError::Connection(err.to_string())
+}
+
+impl EwmhToplevels {
+    pub fn connect() -> Result<Self, ToplevelError> {
+        let (connection, screen) = x11rb::connect(None).map_err(connection_error)?;
+        let root = connection.setup().roots[screen].root;
+        let atoms = Atoms::new(&connection)
+            .map_err(connection_error)?
+            .reply()
+            .map_err(connection_error)?;
+
+        Ok(EwmhToplevels {
+            connection,
+            root,
+            atoms,
+        })
+    }
+
+    fn property(
+        &self,
+        window: Window,
+        property: impl Into<u32>,
+        kind: impl Into<u32>,
+        length: u32,
+    ) -> Result<x11rb::protocol::xproto::GetPropertyReply, ToplevelError> {
+        self.connection
+            .get_property(false, window, property, kind, 0, length)
+            .map_err(connection_error)?
+            .reply()
+            .map_err(connection_error)
+    }
+
+    fn title(&self, window: Window) -> Result<String, ToplevelError> {
+        let title = self.property(
+            window,
+            self.atoms._NET_WM_NAME,
+            self.atoms.UTF8_STRING,
+            TITLE_LENGTH,
+        )?;
+        let title = if title.value.is_empty() {
+            self.property(window, AtomEnum::WM_NAME, AtomEnum::STRING, TITLE_LENGTH)?
+        } else {
+            title
+        };
+
+        Ok(String::from_utf8_lossy(&title.value).to_string())
+    }
+
+    // `WM_CLASS` holds the instance and the class names, separated by a nul byte
+    fn class(&self, window: Window) -> Result<String, ToplevelError> {
+        let class = self.property(window, AtomEnum::WM_CLASS, AtomEnum::STRING, TITLE_LENGTH)?;
+        let class = class
+            .value
+            .split(|byte| *byte == 0)
+            .rfind(|part| !part.is_empty())
+            .unwrap_or_default();
+
+        Ok(String::from_utf8_lossy(class).to_string())
+    }
+
+    fn send(&self, window: Window, message: u32, data: [u32; 5]) -> Result<(), ToplevelError> {
+        let event = ClientMessageEvent::new(32, window, message, data);
+        self.connection
+            .send_event(
+                false,
+                self.root,
+                EventMask::SUBSTRUCTURE_REDIRECT | EventMask::SUBSTRUCTURE_NOTIFY,
+                event,
+            )
+            .map_err(connection_error)?;
+        self.connection.flush().map_err(connection_error)
+    }
+
+    fn window(&self, id: u64) -> Result<Window, ToplevelError> {
+        Window::try_from(id).map_err(|_| ToplevelError::UnknownWindow(id))
+    }
+}
+
+impl ToplevelProvider for EwmhToplevels {
+    fn toplevels(&mut self) -> Result<Vec<Toplevel>, ToplevelError> {
+        let windows = self.property(
+            self.root,
+            self.atoms._NET_CLIENT_LIST,
+            AtomEnum::WINDOW,
+            u32::MAX,
+        )?;
+        let windows: Vec<Window> = windows
+            .value32()
+            .ok_or_else(|| ToplevelError::Unsupported("_NET_CLIENT_LIST is not set".to_string()))?
+            .collect();
+
+        let active = self
+            .property(
+                self.root,
+                self.atoms._NET_ACTIVE_WINDOW,
+                AtomEnum::WINDOW,
+                1,
+            )?
+            .value32()
+            .and_then(|mut windows| windows.next());
+
+        windows
+            .into_iter()
+            .map(|window| {
+                Ok(Toplevel {
+                    id: window as u64,
+                    title: self.title(window)?,
+                    app_id: self.class(window)?,
+                    active: active == Some(window),
+                })
+            })
+            .collect()
+    }
+
+    fn focus(&mut self, id: u64) -> Result<(), ToplevelError> {
+        let window = self.window(id)?;
+        self.send(
+            window,
+            self.atoms._NET_ACTIVE_WINDOW,
+            [SOURCE_PAGER, CURRENT_TIME, 0, 0, 0],
+        )
+    }
+
+    fn close(&mut self, id: u64) -> Result<(), ToplevelError> {
+        let window = self.window(id)?;
+        self.send(
+            window,
+            self.atoms._NET_CLOSE_WINDOW,
+            [CURRENT_TIME, SOURCE_PAGER, 0, 0, 0],
+        )
+    }
+}
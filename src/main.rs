@@ -1,14 +1,16 @@
+use std::io::Read;
 use std::path::PathBuf;
 use std::sync::Mutex;
 
 use anyhow::anyhow;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use once_cell::sync::{Lazy, OnceCell};
-use tracing::{debug, info};
+use tracing::{debug, error, info, warn};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 
-use app::style::Theme;
+use app::style::ThemeHandle;
+use config::themes;
 
 pub mod app;
 pub mod config;
@@ -27,7 +29,12 @@ pub static THEME_PATH: Lazy<Mutex<PathBuf>> = Lazy::new(|| {
 
 static THEME_SCALE: OnceCell<f32> = OnceCell::new();
 
-pub static THEME: Lazy<Theme> = Lazy::new(Theme::load);
+static TOUCH_MODE: OnceCell<bool> = OnceCell::new();
+
+// Results beyond this limit are not displayed
+pub static MAX_RESULTS: OnceCell<usize> = OnceCell::new();
+
+pub static THEME: Lazy<ThemeHandle> = Lazy::new(ThemeHandle::load);
 
 #[derive(Parser)]
 #[command(name = "onagre", author = "Paul D. <paul.delafosse@protonmail.com>")]
@@ -35,15 +42,65 @@ struct Cli {
     #[arg(
         long = "theme",
         short = 't',
-        help = "Path to an alternate onagre theme file"
+        help = "Name of a theme in $XDG_CONFIG_HOME/onagre/themes or path to an alternate onagre theme file"
     )]
-    theme: Option<PathBuf>,
+    theme: Option<String>,
 
     #[arg(long = "scale", short = 's', help = "Change the scale of onagre theme")]
     scale: Option<f32>,
 
+    #[arg(
+        long = "touch",
+        help = "Use the touch profile, whatever the theme says"
+    )]
+    touch: bool,
+
+    #[arg(
+        long = "max-results",
+        help = "Maximum number of results displayed, all results are displayed by default"
+    )]
+    max_results: Option<usize>,
+
     #[arg(long = "mode", short = 'm', help = "The mode parameter as a string")]
     mode: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Manage onagre themes")]
+    Theme {
+        #[command(subcommand)]
+        command: ThemeCommand,
+    },
+    #[command(about = "Manage the clipboard history")]
+    Clipboard {
+        #[command(subcommand)]
+        command: ClipboardCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum ThemeCommand {
+    #[command(about = "List available themes, the current one is marked with '*'")]
+    List,
+}
+
+#[derive(Subcommand)]
+enum ClipboardCommand {
+    #[command(
+        about = "Record the clipboard content read from stdin, e.g. `wl-paste --watch onagre clipboard store`"
+    )]
+    Store {
+        #[arg(
+            long = "mime-types",
+            value_delimiter = ',',
+            help = "MIME types offered with the content, asked to wl-paste on Wayland when missing"
+        )]
+        mime_types: Vec<String>,
+    },
 }
 
 pub fn main() -> iced::Result {
@@ -57,14 +114,40 @@ pub fn main() -> iced::Result {
     info!("Starting onagre");
     let cli = Cli::parse();
 
-    // User defined theme config, $XDG_CONFIG_HOME/onagre/theme.toml otherwise
-    if let Some(theme_path) = cli.theme {
-        let path = theme_path.canonicalize();
-        if let Ok(path) = path {
-            *THEME_PATH.lock().unwrap() = path;
+    if let Some(Command::Theme {
+        command: ThemeCommand::List,
+    }) = cli.command
+    {
+        let current = themes::current().unwrap_or_else(|| themes::DEFAULT_THEME.to_string());
+        for theme in themes::list() {
+            let marker = if theme.name == current { "*" } else { " " };
+            println!("{marker} {}", theme.name);
         }
 
-        info!("Using alternate theme : {:?}", THEME_PATH.lock().unwrap());
+        return Ok(());
+    }
+
+    if let Some(Command::Clipboard {
+        command: ClipboardCommand::Store { mime_types },
+    }) = cli.command
+    {
+        store_clipboard(mime_types);
+        return Ok(());
+    }
+
+    // User defined theme config, the theme picked in the themes mode
+    // or $XDG_CONFIG_HOME/onagre/theme.scss otherwise
+    if let Some(theme) = cli.theme {
+        match themes::resolve(&theme).map(|path| path.canonicalize()) {
+            Some(Ok(path)) => {
+                *THEME_PATH.lock().unwrap() = path;
+                info!("Using alternate theme : {:?}", THEME_PATH.lock().unwrap());
+            }
+            _ => warn!("Theme {theme:?} not found"),
+        }
+    } else if let Some(path) = themes::current().and_then(|name| themes::resolve(&name)) {
+        *THEME_PATH.lock().unwrap() = path;
+        info!("Using theme : {:?}", THEME_PATH.lock().unwrap());
     }
 
     if let Some(scale) = cli.scale {
@@ -72,6 +155,16 @@ pub fn main() -> iced::Result {
         info!("Using scale value : {:?}", scale);
     }
 
+    if cli.touch {
+        TOUCH_MODE.get_or_init(|| true);
+        info!("Using touch profile");
+    }
+
+    if let Some(max_results) = cli.max_results {
+        MAX_RESULTS.get_or_init(|| max_results);
+        info!("Displaying up to {max_results} results");
+    }
+
     if let Some(mode) = cli.mode {
         debug!("Mode parameter: {:?}", mode);
 
@@ -80,3 +173,34 @@ pub fn main() -> iced::Result {
         app::run(None)
     }
 }
+
+// Onagre may have the database open, the content is then spooled
+// and recorded by the next store or by the clipboard mode
+fn store_clipboard(mime_types: Vec<String>) {
+    let mut content = vec![];
+    if let Err(err) = std::io::stdin().read_to_end(&mut content) {
+        error!("Unable to read the clipboard content: {err}");
+        return;
+    }
+
+    let mime_types = if mime_types.is_empty() {
+        freedesktop::clipboard::offered_mime_types()
+    } else {
+        mime_types
+    };
+
+    match db::Database::open() {
+        Ok(db) => {
+            app::native::clipboard::drain_spool(&db);
+            if let Err(err) = app::native::clipboard::store(content, &mime_types, &db) {
+                error!("Unable to store the clipboard content: {err}");
+            }
+        }
+        Err(err) => {
+            debug!("Database unavailable, spooling the clipboard content: {err}");
+            if let Err(err) = app::native::clipboard::spool(content, &mime_types) {
+                error!("Unable to spool the clipboard content: {err}");
+            }
+        }
+    }
+}